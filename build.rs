@@ -0,0 +1,110 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// Embeds every example in `src/days/examples` into the binary, so adding an
+// example only requires adding files there.
+//
+// Each day has a directory `dNN`, containing a `<case>.txt` input file and
+// an optional `<case>.expected` file for each example case.
+//
+// It also records the commit, rustc version and profile of the build, which
+// are saved with the benchmark results.
+
+/// The output of a command, `None` if it couldn't be run or failed.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    match output.status.success() {
+        true => Some(String::from_utf8_lossy(&output.stdout).trim().to_owned()),
+        false => None,
+    }
+}
+
+fn build_info() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    for path in ["src", "Cargo.toml", ".git/HEAD", ".git/index"] {
+        let path = Path::new(&manifest_dir).join(path);
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    // Changes which aren't committed yet are marked by a `-dirty` suffix.
+    let commit = command_output("git", &["rev-parse", "HEAD"]).map(|commit| {
+        match command_output("git", &["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if status.is_empty() => commit,
+            _ => commit + "-dirty",
+        }
+    });
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let rustc_version = command_output(&rustc, &["--version"]);
+
+    println!(
+        "cargo:rustc-env=AOC_GIT_COMMIT={}",
+        commit.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "cargo:rustc-env=AOC_RUSTC_VERSION={}",
+        rustc_version.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "cargo:rustc-env=AOC_PROFILE={}",
+        env::var("PROFILE").unwrap()
+    );
+}
+
+fn main() {
+    build_info();
+
+    let examples_dir =
+        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/days/examples");
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(&examples_dir).expect("Failed to read the examples directory") {
+        let dir = entry.unwrap().path();
+        let day = match dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix('d'))
+            .and_then(|n| n.parse::<u8>().ok())
+        {
+            Some(day) if dir.is_dir() => day,
+            _ => continue,
+        };
+
+        for file in fs::read_dir(&dir).unwrap() {
+            let input = file.unwrap().path();
+            if input.extension().and_then(|e| e.to_str()) != Some("txt") {
+                continue;
+            }
+            let name = input.file_stem().unwrap().to_str().unwrap().to_owned();
+            let expected = input.with_extension("expected");
+            cases.push((day, name, input, expected));
+        }
+    }
+    cases.sort();
+
+    let mut out = String::from("pub static EXAMPLE_FILES: &[(u8, &str, &str, &str)] = &[\n");
+    for (day, name, input, expected) in cases {
+        let expected = match expected.exists() {
+            true => format!("include_str!({:?})", expected.display().to_string()),
+            false => "\"\"".to_owned(),
+        };
+        out += &format!(
+            "    ({}, {:?}, include_str!({:?}), {}),\n",
+            day,
+            name,
+            input.display().to_string(),
+            expected
+        );
+    }
+    out += "];\n";
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs"),
+        out,
+    )
+    .unwrap();
+}
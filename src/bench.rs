@@ -0,0 +1,230 @@
+use crate::days::{Error, InputKind};
+use crate::history::{Delta, Measurement, Step};
+use crate::{dynamic_range_time_format, implemented_day, params_for, ParamArg, Part};
+use colored::*;
+use std::time::{Duration, Instant};
+
+/// How long [`bench_day`] runs a day for.
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Iterations which are run before measuring, to warm up caches.
+    pub warmup: usize,
+    /// A fixed number of measured iterations, otherwise `time` is used.
+    pub iterations: Option<usize>,
+    /// Keep measuring until this much time has passed.
+    pub time: Duration,
+    /// Pin the benchmark to this CPU core, only supported on Linux.
+    pub pin: Option<usize>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: None,
+            time: Duration::from_secs(3),
+            pin: None,
+        }
+    }
+}
+
+/// Statistics over the samples of a single step.
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+    /// Samples below and above the 1.5 IQR fences.
+    outliers: (usize, usize),
+}
+
+impl Stats {
+    fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let secs: Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = match n {
+            1 => 0.0,
+            _ => secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        let q1 = secs[n / 4];
+        let q3 = secs[(n * 3) / 4];
+        let iqr = q3 - q1;
+        let outliers = (
+            secs.iter().filter(|s| **s < q1 - 1.5 * iqr).count(),
+            secs.iter().filter(|s| **s > q3 + 1.5 * iqr).count(),
+        );
+
+        Self {
+            min: sorted[0],
+            median: match n % 2 {
+                0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+                _ => sorted[n / 2],
+            },
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) -> Result<(), String> {
+    // SAFETY: cpu_set_t is a plain bitmask, and only the current thread is changed.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_cpu: usize) -> Result<(), String> {
+    Err("CPU pinning is only supported on Linux".to_owned())
+}
+
+/// Run parsing and the requested parts repeatedly, and print statistics for each of them.
+/// Returns the measurements of the steps which were run.
+pub fn bench_day(
+    day: u8,
+    part: Part,
+    input: &str,
+    params: &[ParamArg],
+    options: &BenchOptions,
+) -> Result<Vec<Measurement>, Error> {
+    println!("{} Day {}", "Benchmarking".green().bold(), day);
+    println!("{}", "-----------------------".green().bold());
+
+    if let Some(cpu) = options.pin {
+        match pin_to_cpu(cpu) {
+            Ok(()) => println!("{}: CPU {}", "Pinned to".green(), cpu),
+            Err(err) => println!("{}: {}", "Couldn't pin to CPU".yellow().bold(), err),
+        }
+    }
+
+    let params = params_for(params, day);
+    let implementation = implemented_day(day)?;
+    let input = input.trim_end();
+
+    for _ in 0..options.warmup {
+        implementation.bench_sample(input, &part, InputKind::Real, &params)?;
+    }
+
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    let start = Instant::now();
+    loop {
+        let done = match options.iterations {
+            Some(n) => samples[0].len() >= n.max(1),
+            None => !samples[0].is_empty() && start.elapsed() >= options.time,
+        };
+        if done {
+            break;
+        }
+
+        let times = implementation.bench_sample(input, &part, InputKind::Real, &params)?;
+        for (samples, time) in samples.iter_mut().zip(times) {
+            samples.push(time);
+        }
+    }
+    let total = start.elapsed();
+
+    println!(
+        "\t{}: {} iterations",
+        "Warmup".green(),
+        options.warmup.to_string().bold().blue()
+    );
+    println!(
+        "\t{}: {} iterations in {}",
+        "Measured".green(),
+        samples[0].len().to_string().bold().blue(),
+        dynamic_range_time_format(&total).bold().blue()
+    );
+    println!(
+        "\t{}",
+        format!(
+            "{:<8} {:<10} {:<10} {:<10} {:<10} Outliers",
+            "", "Min", "Median", "Mean", "Std. dev."
+        )
+        .green()
+        .bold()
+    );
+
+    let mut measurements = Vec::new();
+    for (i, step) in [Step::Parsing, Step::One, Step::Two]
+        .into_iter()
+        .enumerate()
+    {
+        if (step == Step::One && part == Part::Two) || (step == Step::Two && part == Part::One) {
+            continue;
+        }
+        let stats = Stats::new(&samples[i]);
+        println!(
+            "\t{} {} {} {} {} {}",
+            format!("{:<8}", step.to_string()).green(),
+            format!("{:<10}", dynamic_range_time_format(&stats.min))
+                .bold()
+                .blue(),
+            format!("{:<10}", dynamic_range_time_format(&stats.median))
+                .bold()
+                .blue(),
+            format!("{:<10}", dynamic_range_time_format(&stats.mean))
+                .bold()
+                .blue(),
+            format!("{:<10}", dynamic_range_time_format(&stats.stddev))
+                .bold()
+                .blue(),
+            match stats.outliers {
+                (0, 0) => "none".to_owned(),
+                (low, high) => format!("{} low, {} high", low, high),
+            }
+        );
+        measurements.push(Measurement {
+            day,
+            step,
+            min: stats.min,
+            median: stats.median,
+        });
+    }
+
+    Ok(measurements)
+}
+
+/// Print how the medians changed compared to an earlier benchmark,
+/// returns whether none of the steps got slower by more than `threshold` percent.
+pub fn print_comparison(reference: &str, deltas: &[Delta], threshold: f64) -> bool {
+    println!("\t{} {}", "Compared to".green(), reference.bold());
+    let mut ok = true;
+    for delta in deltas {
+        let change = format!("{:+.1}%", delta.change);
+        let change = match delta.change {
+            c if c > threshold => {
+                ok = false;
+                format!("{} {}", change.red().bold(), "SLOWER".red().bold())
+            }
+            c if c < -threshold => change.green().bold().to_string(),
+            _ => change.bold().to_string(),
+        };
+        println!(
+            "\t{} {} -> {} {}",
+            format!("{:<15}", format!("Day {} {}", delta.day, delta.step)).green(),
+            format!("{:<10}", dynamic_range_time_format(&delta.before))
+                .bold()
+                .blue(),
+            format!("{:<10}", dynamic_range_time_format(&delta.after))
+                .bold()
+                .blue(),
+            change
+        );
+    }
+    if deltas.is_empty() {
+        println!("\t{}", "No earlier results of these steps".yellow());
+    }
+    ok
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bodies AoC sends instead of an input, they must never end up in the cache.
+const ERROR_PAGES: &[&str] = &[
+    "Please log in to get your puzzle input",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+    "500 Internal Server Error",
+];
+
+/// Check that a downloaded text looks like a puzzle input and not like an error page.
+pub fn check_input(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Err("the input is empty".to_owned());
+    }
+    if let Some(page) = ERROR_PAGES.iter().find(|p| input.contains(*p)) {
+        return Err(format!("the server responded with an error: {}", page));
+    }
+    let start = input.trim_start().to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err("the server responded with a html page".to_owned());
+    }
+    Ok(())
+}
+
+/// 64 bit FNV-1a, stable across Rust versions unlike the std hashers.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn checksum(input: &str) -> String {
+    format!("{:016x}", fnv1a(input.as_bytes()))
+}
+
+/// Inputs differ by user, so entries are kept apart by a hash of the session instead of the session itself.
+pub fn session_hash(session: &str) -> String {
+    format!("{:016x}", fnv1a(session.trim().as_bytes()))
+}
+
+/// Write a file by writing a temporary file next to it and renaming it,
+/// so a crash never leaves a half written file behind.
+fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, content)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// A cached input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub year: u16,
+    pub day: u8,
+    pub session_hash: String,
+    pub path: PathBuf,
+}
+
+impl CacheEntry {
+    fn checksum_path(&self) -> PathBuf {
+        self.path.with_extension("sum")
+    }
+
+    /// Read the input, checking it against its checksum.
+    pub fn read(&self) -> Result<String, String> {
+        let input = fs::read_to_string(&self.path)
+            .map_err(|err| format!("{}: {}", self.path.display(), err))?;
+        let sum = fs::read_to_string(self.checksum_path())
+            .map_err(|_| "the checksum is missing".to_owned())?;
+        if sum.trim() != checksum(&input) {
+            return Err("the checksum doesn't match".to_owned());
+        }
+        check_input(&input)?;
+        Ok(input)
+    }
+
+    pub fn remove(&self) -> Result<(), String> {
+        for path in [self.path.clone(), self.checksum_path()] {
+            match fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(format!("{}: {}", path.display(), err))
+                }
+                _ => {}
+            }
+        }
+        if let Some(dir) = self.path.parent() {
+            // Only succeeds once the session has no entries left.
+            let _ = fs::remove_dir(dir);
+        }
+        Ok(())
+    }
+}
+
+/// Downloaded inputs, stored as `inputs/<year>/<session hash>/<day>.txt`
+/// with a `<day>.sum` checksum next to each of them.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.join("inputs"),
+        }
+    }
+
+    pub fn entry(&self, year: u16, day: u8, session: &str) -> CacheEntry {
+        let session_hash = session_hash(session);
+        CacheEntry {
+            year,
+            day,
+            path: self
+                .dir
+                .join(year.to_string())
+                .join(&session_hash)
+                .join(format!("{:02}.txt", day)),
+            session_hash,
+        }
+    }
+
+    /// The cached input, `None` if it isn't cached or the entry is broken.
+    pub fn get(&self, year: u16, day: u8, session: &str) -> Option<String> {
+        self.entry(year, day, session).read().ok()
+    }
+
+    /// Cache an input, error pages are refused.
+    pub fn put(&self, year: u16, day: u8, session: &str, input: &str) -> Result<(), String> {
+        check_input(input)?;
+        let entry = self.entry(year, day, session);
+        if let Some(dir) = entry.path.parent() {
+            fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+        }
+        write_atomic(&entry.path, input)?;
+        write_atomic(&entry.checksum_path(), &checksum(input))
+    }
+
+    /// All cached inputs, sorted by year, day and session.
+    pub fn entries(&self) -> Vec<CacheEntry> {
+        let read_dir = |path: &Path| {
+            fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|e| e.path())
+                .collect::<Vec<_>>()
+        };
+
+        let mut entries = Vec::new();
+        for year_dir in read_dir(&self.dir) {
+            let Some(year) = year_dir.file_name().and_then(|n| n.to_str()?.parse().ok()) else {
+                continue;
+            };
+            for session_dir in read_dir(&year_dir) {
+                let Some(session_hash) = session_dir.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let session_hash = session_hash.to_owned();
+                for path in read_dir(&session_dir) {
+                    if path.extension().is_none_or(|e| e != "txt") {
+                        continue;
+                    }
+                    let Some(day) = path.file_stem().and_then(|n| n.to_str()?.parse().ok()) else {
+                        continue;
+                    };
+                    entries.push(CacheEntry {
+                        year,
+                        day,
+                        session_hash: session_hash.clone(),
+                        path,
+                    });
+                }
+            }
+        }
+        entries.sort_by(|a, b| {
+            (a.year, a.day, &a.session_hash).cmp(&(b.year, b.day, &b.session_hash))
+        });
+        entries
+    }
+
+    /// Move the inputs of the old `.aoc21_cache` directory into the cache,
+    /// they are assumed to belong to `year` and `session`. Returns the number of moved inputs.
+    pub fn migrate_inputs(&self, old: &Path, year: u16, session: &str) -> Result<usize, String> {
+        let mut moved = 0;
+        for day in 1..=25 {
+            let path = old.join(format!("input{:02}.txt", day));
+            let Ok(input) = fs::read_to_string(&path) else {
+                continue;
+            };
+            // Error pages which were cached by older versions are dropped.
+            if check_input(&input).is_ok() && self.get(year, day, session).is_none() {
+                self.put(year, day, session, &input)?;
+                moved += 1;
+            }
+            fs::remove_file(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        }
+        Ok(moved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty cache directory only used by one test.
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc22-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn accepts_inputs() {
+        assert_eq!(check_input("1000\n2000\n\n3000\n"), Ok(()));
+        assert_eq!(
+            check_input("Sensor at x=2, y=18: closest beacon is at x=-2, y=15"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn refuses_error_pages() {
+        assert_eq!(check_input(""), Err("the input is empty".to_owned()));
+        assert_eq!(check_input(" \n\n"), Err("the input is empty".to_owned()));
+        assert_eq!(
+            check_input("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(
+                "the server responded with an error: Please log in to get your puzzle input"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            check_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+            Err("the server responded with an error: Please don't repeatedly request this endpoint before it unlocks".to_owned())
+        );
+        assert_eq!(
+            check_input("404 Not Found\n"),
+            Err("the server responded with an error: 404 Not Found".to_owned())
+        );
+        assert_eq!(
+            check_input("\n<!DOCTYPE html>\n<html lang=\"en-us\"><body></body></html>"),
+            Err("the server responded with a html page".to_owned())
+        );
+        assert_eq!(
+            check_input("<HTML><body></body></HTML>"),
+            Err("the server responded with a html page".to_owned())
+        );
+    }
+
+    #[test]
+    fn reads_what_was_put() {
+        let dir = cache_dir("roundtrip");
+        let cache = Cache::new(&dir);
+        cache.put(2022, 1, "session", "1000\n2000\n").unwrap();
+
+        assert_eq!(
+            cache.get(2022, 1, "session").as_deref(),
+            Some("1000\n2000\n")
+        );
+        assert_eq!(cache.get(2022, 1, "other session"), None);
+        assert_eq!(cache.get(2021, 1, "session"), None);
+        assert_eq!(cache.entries(), [cache.entry(2022, 1, "session")]);
+
+        assert!(cache.put(2022, 2, "session", "404 Not Found").is_err());
+        assert_eq!(cache.entries().len(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn detects_checksum_mismatch() {
+        let dir = cache_dir("checksum");
+        let cache = Cache::new(&dir);
+        cache
+            .put(2022, 3, "session", "vJrwpWtwJgWrhcsFMMfFFhFp\n")
+            .unwrap();
+        let entry = cache.entry(2022, 3, "session");
+
+        fs::write(&entry.path, "vJrwpWtwJgWrhcsFMMfFFhF\n").unwrap();
+        assert_eq!(entry.read(), Err("the checksum doesn't match".to_owned()));
+        assert_eq!(cache.get(2022, 3, "session"), None);
+
+        fs::remove_file(entry.checksum_path()).unwrap();
+        assert_eq!(entry.read(), Err("the checksum is missing".to_owned()));
+
+        entry.remove().unwrap();
+        assert!(entry.read().is_err());
+        assert!(cache.entries().is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
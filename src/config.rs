@@ -0,0 +1,231 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where inputs are downloaded from and where they are cached.
+///
+/// Each setting is taken from the first of these that has it:
+/// command line flags (applied by the caller), environment variables,
+/// the config file and the defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The AoC browser session, `AOC_SESSION` or `session`.
+    pub session: Option<String>,
+    /// The year of the puzzles, `AOC_YEAR` or `year`.
+    pub year: u16,
+    /// The AoC server, `AOC_BASE_URL` or `base_url`.
+    pub base_url: String,
+    /// Where inputs, answers, attempts and benchmark results are stored, `AOC_CACHE_DIR` or `cache_dir`.
+    pub cache_dir: PathBuf,
+}
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            year: 2022,
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            cache_dir: dirs::cache_dir()
+                .map(|dir| dir.join("aoc22"))
+                .unwrap_or_else(|| PathBuf::from(".aoc22_cache")),
+        }
+    }
+}
+
+impl Config {
+    /// The config file used if none is given, `$XDG_CONFIG_HOME/aoc22/config` on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("aoc22").join("config"))
+    }
+
+    /// Load the config file and apply the environment variables.
+    /// Without `path`, `AOC_CONFIG` or the default path is used, which may be missing.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        let explicit = path
+            .map(|p| p.to_owned())
+            .or_else(|| env::var_os("AOC_CONFIG").map(PathBuf::from));
+        match (explicit, Self::default_path()) {
+            (Some(path), _) => {
+                let content = fs::read_to_string(&path)
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+                config.apply_file(&path, &content)?;
+            }
+            (None, Some(path)) => match fs::read_to_string(&path) {
+                Ok(content) => config.apply_file(&path, &content)?,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(format!("{}: {}", path.display(), err)),
+            },
+            (None, None) => {}
+        }
+
+        for (key, var) in [
+            ("session", "AOC_SESSION"),
+            ("year", "AOC_YEAR"),
+            ("base_url", "AOC_BASE_URL"),
+            ("cache_dir", "AOC_CACHE_DIR"),
+        ] {
+            if let Ok(value) = env::var(var) {
+                config
+                    .set(key, &value)
+                    .map_err(|err| format!("{}: {}", var, err))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Apply `key = value` lines, `#` starts a comment.
+    fn apply_file(&mut self, path: &Path, content: &str) -> Result<(), String> {
+        for (i, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            line.split_once('=')
+                .ok_or_else(|| "expected `key = value`".to_owned())
+                .and_then(|(key, value)| self.set(key.trim(), value.trim()))
+                .map_err(|err| format!("{}, line {}: {}", path.display(), i + 1, err))?;
+        }
+        Ok(())
+    }
+
+    /// Set a single setting by the name it has in the config file.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "session" => self.session = Some(value.to_owned()),
+            "year" => {
+                self.year = value
+                    .parse()
+                    .ok()
+                    .filter(|year| *year >= 2015)
+                    .ok_or_else(|| format!("`{}` is not a valid year", value))?
+            }
+            "base_url" => {
+                value
+                    .parse::<reqwest::Url>()
+                    .map_err(|err| format!("invalid base url `{}`: {}", value, err))?;
+                self.base_url = value.trim_end_matches('/').to_owned();
+            }
+            "cache_dir" => self.cache_dir = PathBuf::from(value),
+            _ => {
+                return Err(format!(
+                    "unknown setting `{}`, expected session, year, base_url or cache_dir",
+                    key
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// The url of a page of a day, like `input` or `answer`, an empty page is the puzzle itself.
+    pub fn day_url(&self, day: u8, page: &str) -> String {
+        match page {
+            "" => format!("{}/{}/day/{}", self.base_url, self.year, day),
+            _ => format!("{}/{}/day/{}/{}", self.base_url, self.year, day, page),
+        }
+    }
+
+    /// Where the text and the example candidates of a puzzle are saved.
+    pub fn puzzle_dir(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join("puzzles")
+            .join(self.year.to_string())
+            .join(format!("{:02}", day))
+    }
+
+    pub fn ledger_path(&self) -> PathBuf {
+        self.cache_dir.join("answers.tsv")
+    }
+
+    pub fn attempts_path(&self) -> PathBuf {
+        self.cache_dir.join("attempts.tsv")
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        self.cache_dir.join("history.tsv")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARS: [&str; 4] = ["AOC_SESSION", "AOC_YEAR", "AOC_BASE_URL", "AOC_CACHE_DIR"];
+
+    fn write_config(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc22-test-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    // Environment variables are shared by all tests, so everything reading them is in this test.
+    #[test]
+    fn flags_override_env_which_overrides_file() {
+        for var in VARS {
+            env::remove_var(var);
+        }
+        let path = write_config(
+            "precedence",
+            "# my settings\nsession = from-file\nyear = 2019 # comment\nbase_url = http://localhost:8080/\n",
+        );
+
+        let config = Config::load(Some(&path)).unwrap();
+        assert_eq!(config.session.as_deref(), Some("from-file"));
+        assert_eq!(config.year, 2019);
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.cache_dir, Config::default().cache_dir);
+
+        env::set_var("AOC_YEAR", "2020");
+        env::set_var("AOC_CACHE_DIR", "/tmp/from-env");
+        let mut config = Config::load(Some(&path)).unwrap();
+        assert_eq!(config.session.as_deref(), Some("from-file"));
+        assert_eq!(config.year, 2020);
+        assert_eq!(config.cache_dir, PathBuf::from("/tmp/from-env"));
+
+        // Flags are applied by the caller after loading.
+        config.set("year", "2021").unwrap();
+        assert_eq!(config.year, 2021);
+        assert_eq!(config.cache_dir, PathBuf::from("/tmp/from-env"));
+
+        env::set_var("AOC_YEAR", "1999");
+        let err = Config::load(Some(&path)).unwrap_err();
+        assert!(err.starts_with("AOC_YEAR: "), "{}", err);
+
+        for var in VARS {
+            env::remove_var(var);
+        }
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn invalid_file() {
+        let path = write_config("invalid", "year = 2022\n\ncolour = blue\n");
+        let err = Config::default()
+            .apply_file(&path, &fs::read_to_string(&path).unwrap())
+            .unwrap_err();
+        assert!(
+            err.ends_with(
+                "line 3: unknown setting `colour`, expected session, year, base_url or cache_dir"
+            ),
+            "{}",
+            err
+        );
+        let _ = fs::remove_file(path);
+
+        let path = env::temp_dir().join("aoc22-test-missing-config");
+        assert!(Config::load(Some(&path)).is_err());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let mut config = Config::default();
+        assert!(config.set("year", "2014").is_err());
+        assert!(config.set("year", "next").is_err());
+        assert!(config.set("base_url", "not a url").is_err());
+        assert_eq!(config, Config::default());
+    }
+}
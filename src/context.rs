@@ -0,0 +1,137 @@
+use crate::days::InputKind;
+use crate::log::{self, Level, Scope};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Tells a part which took too long to stop, see [`RunContext::is_cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far a part has come, see [`RunContext::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub day: u8,
+    /// `None` while parsing.
+    pub part: Option<u8>,
+    pub done: u64,
+    pub total: u64,
+}
+
+/// Receives the progress of long running parts, for example to show it.
+/// It is called from the threads the parts run on.
+pub type ProgressSink = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// Everything a day gets besides its input: the parameters, how much to log, whether to stop
+/// and where to report progress. Every run has its own context, so days which run at the
+/// same time don't affect each other, even with different settings.
+#[derive(Clone)]
+pub struct RunContext<P> {
+    params: P,
+    kind: InputKind,
+    scope: Scope,
+    cancel: CancelToken,
+    progress: Option<ProgressSink>,
+}
+
+impl<P> RunContext<P> {
+    /// A context for parsing, using the log level of the day set up by [`init_logging`](crate::init_logging).
+    pub fn new(day: u8, kind: InputKind, params: P) -> Self {
+        Self {
+            params,
+            kind,
+            scope: Scope {
+                day,
+                part: None,
+                level: log::default_level(day),
+            },
+            cancel: CancelToken::default(),
+            progress: None,
+        }
+    }
+
+    /// Log only messages at least as important as `level`.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.scope.level = level;
+        self
+    }
+
+    pub fn with_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    pub fn params(&self) -> &P {
+        &self.params
+    }
+
+    /// Whether the input is an example or the real input.
+    pub fn kind(&self) -> InputKind {
+        self.kind
+    }
+
+    pub fn day(&self) -> u8 {
+        self.scope.day
+    }
+
+    /// The running part, `None` while parsing.
+    pub fn part(&self) -> Option<u8> {
+        self.scope.part
+    }
+
+    /// The least important messages which are logged, useful to skip expensive debug output.
+    pub fn level(&self) -> Level {
+        self.scope.level
+    }
+
+    /// Whether the part took too long. Long loops should check this and return early,
+    /// the answer is thrown away anyway.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    /// Report that `done` of `total` steps are done, does nothing if nobody is interested.
+    pub fn progress(&self, done: u64, total: u64) {
+        if let Some(sink) = &self.progress {
+            sink(Progress {
+                day: self.scope.day,
+                part: self.scope.part,
+                done,
+                total,
+            });
+        }
+    }
+
+    /// Where the messages of this context are attributed to.
+    pub(crate) fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// A context for a part, with its own cancel token.
+    pub(crate) fn for_part(&self, part: u8) -> Self
+    where
+        P: Clone,
+    {
+        Self {
+            scope: Scope {
+                part: Some(part),
+                ..self.scope
+            },
+            cancel: CancelToken::default(),
+            ..self.clone()
+        }
+    }
+}
@@ -1,37 +1,32 @@
-use super::{Answer, Day, DayImpl};
+use super::{number, Answer, Day, DayImpl, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 1;
 
 type Data = Vec<Vec<u64>>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test01.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(24000), Answer::Number(45000))
-    }
+    type Params = ();
 
-    fn init(input: &str) -> (Self, Data) {
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
         let mut o: Vec<Vec<u64>> = Vec::new();
 
-        input.split("\n\n").for_each(|v| {
-            let v: Vec<u64> = v
+        for v in input.split("\n\n") {
+            let v = v
                 .lines()
-                .map(|v| v.parse::<u64>().expect("error while parsing input."))
-                .collect();
+                .map(number)
+                .collect::<ParseResult<Vec<u64>>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?;
             o.push(v);
-        });
+        }
 
-        (Self {}, o)
+        Ok((Self {}, o))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let n: u64 = data.iter().map(|v| v.iter().sum()).max().unwrap();
         Answer::Number(n)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut d: Vec<u64> = data.iter().map(|v| v.iter().sum::<u64>()).collect();
         d.sort_by(|a, b| b.cmp(a));
         Answer::Number(d[0] + d[1] + d[2])
@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 2;
 
@@ -80,41 +80,45 @@ impl Hand {
     }
 }
 
-impl From<&str> for Hand {
-    fn from<'a>(s: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Hand {
+    type Error = InputError<'a>;
+
+    fn try_from(s: &'a str) -> ParseResult<'a, Self> {
         match s {
-            "A" => Hand::StoneOrLoose,
-            "B" => Hand::PaperOrDraw,
-            "C" => Hand::ScissorsOrWin,
-            "X" => Hand::StoneOrLoose,
-            "Y" => Hand::PaperOrDraw,
-            "Z" => Hand::ScissorsOrWin,
-            _ => panic!("Invalid input while parsing"),
+            "A" => Ok(Hand::StoneOrLoose),
+            "B" => Ok(Hand::PaperOrDraw),
+            "C" => Ok(Hand::ScissorsOrWin),
+            "X" => Ok(Hand::StoneOrLoose),
+            "Y" => Ok(Hand::PaperOrDraw),
+            "Z" => Ok(Hand::ScissorsOrWin),
+            _ => Err(InputError::new(s, "expected one of A, B, C, X, Y or Z")),
         }
     }
 }
 
 type Data = Vec<Vec<Hand>>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test02.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(15), Answer::Number(12))
-    }
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        let mut games = Vec::new();
+
+        for l in input.lines() {
+            let game = l
+                .split_whitespace()
+                .map(Hand::try_from)
+                .collect::<ParseResult<Vec<Hand>>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?;
+            if game.len() != 2 {
+                return Err(InputError::new(l, "expected exactly two hands").locate(CURRENT_DAY, input));
+            }
+            games.push(game);
+        }
 
-    fn init(input: &str) -> (Self, Data) {
-        (
-            Self {},
-            input
-                .lines()
-                .map(|v| v.split_whitespace().map(Hand::from).collect())
-                .collect(),
-        )
+        Ok((Self {}, games))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut score = 0;
         for g in data {
             score += g[1].play(&g[0]).score() + g[1].score();
@@ -122,7 +126,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(score)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut score = 0;
         for g in data {
             score += g[1].get_part2_matching(&g[0]).score() + g[1].result_score();
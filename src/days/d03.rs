@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{Answer, Day, DayImpl, InputError, ParseError, RunContext};
 use std::collections::BTreeSet;
 
 const CURRENT_DAY: u8 = 3;
@@ -13,28 +13,29 @@ fn get_character_priority(c: &char) -> u64 {
 
 type Data = Vec<(BTreeSet<char>, BTreeSet<char>)>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test03.txt"))
-    }
+    type Params = ();
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(157), Answer::Number(70))
-    }
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        let mut rucksacks = Vec::new();
+
+        for v in input.lines() {
+            if let Some((i, c)) = v.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(InputError::new(&v[i..i + c.len_utf8()], "expected a letter")
+                    .locate(CURRENT_DAY, input));
+            }
+            if !v.len().is_multiple_of(2) {
+                return Err(InputError::new(v, "expected an even number of items")
+                    .locate(CURRENT_DAY, input));
+            }
+
+            let a = v.split_at(v.len() / 2);
+            rucksacks.push((a.0.chars().collect(), a.1.chars().collect()));
+        }
 
-    fn init(input: &str) -> (Self, Data) {
-        (
-            Self {},
-            input
-                .lines()
-                .map(|v| {
-                    let a = v.split_at(v.len() / 2);
-                    (a.0.chars().collect(), a.1.chars().collect())
-                })
-                .collect(),
-        )
+        Ok((Self {}, rucksacks))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut sum = 0;
 
         for s in data {
@@ -43,7 +44,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(sum)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut sum = 0;
 
         // And this next thing took way too long to code...
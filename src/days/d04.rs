@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 4;
 
@@ -15,37 +15,41 @@ impl Range {
     }
 }
 
-impl From<&str> for Range {
-    fn from(v: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Range {
+    type Error = InputError<'a>;
+
+    fn try_from(v: &'a str) -> ParseResult<'a, Self> {
         let mut s = v.split('-');
-        Self(
-            s.next().unwrap().parse().unwrap(),
-            s.next().unwrap().parse().unwrap(),
-        )
+        Ok(Self(
+            number(token(s.next(), v, "a section")?)?,
+            number(token(s.next(), v, "`-`")?)?,
+        ))
     }
 }
 
 type Data = Vec<Vec<Range>>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test04.txt"))
-    }
+    type Params = ();
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(2), Answer::Number(4))
-    }
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        let mut pairs = Vec::new();
+
+        for l in input.lines() {
+            let pair = l
+                .split(',')
+                .map(Range::try_from)
+                .collect::<ParseResult<Vec<Range>>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?;
+            if pair.len() != 2 {
+                return Err(InputError::new(l, "expected a pair of ranges").locate(CURRENT_DAY, input));
+            }
+            pairs.push(pair);
+        }
 
-    fn init(input: &str) -> (Self, Data) {
-        (
-            Self {},
-            input
-                .lines()
-                .map(|v| v.split(',').map(Range::from).collect())
-                .collect(),
-        )
+        Ok((Self {}, pairs))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut c = 0;
         for d in data {
             if d[0].contains(&d[1]) || d[1].contains(&d[0]) {
@@ -55,7 +59,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(c)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut c = 0;
         for d in data {
             if d[0].overlaps(&d[1]) {
@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 use std::collections::{BTreeMap, VecDeque};
 
 const CURRENT_DAY: u8 = 5;
@@ -36,67 +36,59 @@ impl Command {
     }
 }
 
-impl From<&str> for Command {
-    fn from(s: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Command {
+    type Error = InputError<'a>;
+
+    fn try_from(s: &'a str) -> ParseResult<'a, Self> {
         let mut parts = s.split(' ');
-        Self {
-            num: parts.nth(1).unwrap().parse().unwrap(),
-            from: parts.nth(1).unwrap().parse().unwrap(),
-            to: parts.nth(1).unwrap().parse().unwrap(),
-        }
+        Ok(Self {
+            num: number(token(parts.nth(1), s, "a crate count")?)?,
+            from: number(token(parts.nth(1), s, "a source stack")?)?,
+            to: number(token(parts.nth(1), s, "a target stack")?)?,
+        })
     }
 }
 
 type Data = (Stack, Vec<Command>);
 
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test05.txt"))
-    }
+    type Params = ();
 
-    fn expected_results() -> (Answer, Answer) {
-        (
-            Answer::String("CMZ".to_owned()),
-            Answer::String("MCD".to_owned()),
-        )
-    }
-
-    fn init(input: &str) -> (Self, Data) {
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
         let mut stacks: Stack = Stack::new();
 
         let mut s_input = input.split("\n\n");
 
         // --- PARSE STACKS ---
 
-        let stacks_input = s_input.next().unwrap().lines();
-        let height = stacks_input.clone().count() - 1;
+        let stacks_input = s_input.next().unwrap_or_default().lines();
+        let height = stacks_input.clone().count().saturating_sub(1);
 
-        for (i, mut l) in stacks_input.enumerate() {
+        for (i, l) in stacks_input.enumerate() {
             if i == height {
+                // The last line numbers the stacks, some of which might start out empty.
+                for n in l.split_whitespace() {
+                    stacks.entry(number(n).map_err(|e| e.locate(CURRENT_DAY, input))?).or_default();
+                }
                 break;
             }
-            let mut run = true;
-
-            let mut n: u8 = 1;
-            while run {
-                let p = match l.len() {
-                    3 => {
-                        run = false;
-                        l
-                    }
-                    _ => {
-                        let (p, n_l) = l.split_at(4);
-                        l = n_l;
-                        &p[..3]
-                    }
-                };
+            if !l.is_ascii() {
+                return Err(InputError::new(l, "expected crates like `[A]`").locate(CURRENT_DAY, input));
+            }
 
-                let c = p.chars().nth(1).unwrap();
-                if c != ' ' {
-                    let v = stacks.entry(n).or_insert(VecDeque::new());
-                    v.push_back(c);
+            // Every crate takes up 3 characters, followed by a space.
+            for (n, start) in (0..l.len()).step_by(4).enumerate() {
+                let p = &l[start..(start + 3).min(l.len())];
+                if p.trim().is_empty() {
+                    continue;
+                }
+                if p.len() != 3 || !p.starts_with('[') || !p.ends_with(']') {
+                    return Err(InputError::new(p, "expected a crate like `[A]`").locate(CURRENT_DAY, input));
                 }
-                n += 1;
+
+                let c = p.chars().nth(1).unwrap();
+                let v = stacks.entry(n as u8 + 1).or_default();
+                v.push_back(c);
             }
         }
 
@@ -104,14 +96,25 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
 
         let mut commands = Vec::new();
 
-        for l in s_input.next().unwrap().lines() {
-            commands.push(Command::from(l));
+        let commands_input = s_input.next().ok_or_else(|| {
+            InputError::missing(input, "an empty line followed by the rearrangement procedure")
+                .locate(CURRENT_DAY, input)
+        })?;
+        for l in commands_input.lines() {
+            let command = Command::try_from(l).map_err(|e| e.locate(CURRENT_DAY, input))?;
+            for stack in [command.from, command.to] {
+                if !stacks.contains_key(&stack) {
+                    return Err(InputError::new(l, format!("there is no stack {}", stack))
+                        .locate(CURRENT_DAY, input));
+                }
+            }
+            commands.push(command);
         }
 
-        (Self {}, (stacks, commands))
+        Ok((Self {}, (stacks, commands)))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         for cmd in &data.1 {
             cmd.run9000(&mut data.0);
         }
@@ -125,7 +128,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::String(output)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         for cmd in &data.1 {
             cmd.run9001(&mut data.0);
         }
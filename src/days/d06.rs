@@ -1,63 +1,37 @@
-use super::{Answer, Day, DayImpl};
-use std::collections::VecDeque;
+use super::{Answer, Day, DayImpl, InputError, ParseError, RunContext};
 
 const CURRENT_DAY: u8 = 6;
 
-fn contains_duplicate(queue: &VecDeque<char>) -> bool {
-    for v in queue {
-        if queue.iter().filter(|x| x.eq(&v)).count() > 1 {
-            return true;
-        }
-    }
-
-    false
+/// The number of characters up to and including the first `len` distinct characters in a row.
+fn find_marker(signal: &str, len: usize) -> Option<u64> {
+    let chars: Vec<char> = signal.chars().collect();
+    chars
+        .windows(len)
+        .position(|w| w.iter().enumerate().all(|(i, c)| !w[i + 1..].contains(c)))
+        .map(|i| (i + len) as u64)
 }
 
 type Data = String;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test06.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(11), Answer::Number(26))
-    }
-
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, input.to_owned())
-    }
-
-    fn one(&self, data: &mut Data) -> Answer {
-        let mut chars = data.chars();
-        let mut queue: VecDeque<char> = VecDeque::new();
-        let mut i = 1;
-
-        loop {
-            queue.push_back(chars.next().unwrap());
-            if queue.len() > 4 {
-                queue.pop_front();
-                if !contains_duplicate(&queue) {
-                    return Answer::Number(i);
-                }
-            }
-            i += 1;
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        // Both parts need a full window of their marker length, part 2 the longer one.
+        let signal = input.trim_end();
+        if signal.chars().count() < 14 {
+            return Err(InputError::missing(signal, "a signal of at least 14 characters")
+                .locate(CURRENT_DAY, input));
         }
+        Ok((Self {}, signal.to_owned()))
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
-        let mut chars = data.chars();
-        let mut queue: VecDeque<char> = VecDeque::new();
-        let mut i = 1;
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        let marker = find_marker(data, 4).expect("there is no start-of-packet marker");
+        Answer::Number(marker)
+    }
 
-        loop {
-            queue.push_back(chars.next().unwrap());
-            if queue.len() > 14 {
-                queue.pop_front();
-                if !contains_duplicate(&queue) {
-                    return Answer::Number(i);
-                }
-            }
-            i += 1;
-        }
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        let marker = find_marker(data, 14).expect("there is no start-of-message marker");
+        Answer::Number(marker)
     }
 }
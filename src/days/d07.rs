@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 use std::collections::BTreeMap;
 
 // For your own sanity, don't read this... This is absolute bullshit
@@ -19,7 +19,6 @@ pub struct File {
     is_dir: bool,
     size: Option<u64>,
     children: BTreeMap<String, File>,
-    parent: Option<*mut File>,
 }
 
 impl File {
@@ -28,7 +27,6 @@ impl File {
             is_dir,
             size,
             children: BTreeMap::new(),
-            parent: None,
         }
     }
 
@@ -67,12 +65,17 @@ impl File {
             .sum()
     }
 
-    fn insert_child(&mut self, name: &str, mut child: File) -> &mut File {
-        child.parent = Some(self as *mut File);
+    fn insert_child(&mut self, name: &str, child: File) -> &mut File {
         self.children.insert(name.to_owned(), child);
         self.children.get_mut(name).unwrap()
     }
 
+    /// The directory at `path` below this one, it has to exist.
+    fn get_mut(&mut self, path: &[&str]) -> &mut File {
+        path.iter()
+            .fold(self, |dir, name| dir.children.get_mut(*name).unwrap())
+    }
+
     // fuck this function
     fn task_one(&self) -> u64 {
         let mut sum = 0;
@@ -109,52 +112,57 @@ impl File {
     }
 }
 
-type Data = File;
-impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test07.txt"))
+fn parse_tree<'a>(root: &mut File, input: &'a str) -> ParseResult<'a, ()> {
+    // The names of the directories from the root to the current one.
+    let mut path: Vec<&str> = Vec::new();
+
+    for line in input.lines() {
+        let mut l = line.split_ascii_whitespace();
+        let a = token(l.next(), line, "a command or a directory listing")?;
+        if a.starts_with('$') {
+            match token(l.next(), line, "a command")? {
+                "cd" => match token(l.next(), line, "a directory name")? {
+                    "/" => path.clear(),
+                    ".." => {
+                        path.pop().ok_or_else(|| {
+                            InputError::new(line, "can't leave the root directory")
+                        })?;
+                    }
+                    name => {
+                        let f = File::new(true, None);
+                        root.get_mut(&path).insert_child(name, f);
+                        path.push(name);
+                    }
+                },
+                "ls" => {}
+                c => return Err(InputError::new(c, "expected `cd` or `ls`")),
+            }
+        } else if a != "dir" {
+            // This can only be ls output.
+            let f = File::new(false, Some(number(a)?));
+            root.get_mut(&path)
+                .insert_child(token(l.next(), line, "a file name")?, f);
+        }
     }
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(95437), Answer::Number(24933642))
-    }
+    Ok(())
+}
 
-    fn init(input: &str) -> (Self, Data) {
-        let mut root: File = File::new(true, None);
-        let mut current_file = &mut root;
-
-        for mut l in input.lines().map(|v| v.split_ascii_whitespace()) {
-            let a = l.next().unwrap();
-            if a.starts_with('$') {
-                match l.next().unwrap() {
-                    "cd" => match l.next().unwrap() {
-                        "/" => current_file = &mut root,
-                        ".." => current_file = unsafe { &mut *current_file.parent.unwrap() },
-                        name => {
-                            let f = File::new(true, None);
-                            current_file = current_file.insert_child(name, f);
-                        }
-                    },
-                    "ls" => {}
-                    _ => panic!("unexpected command"),
-                }
-            } else {
-                // This can only be ls output.
-                if let Ok(size) = a.parse::<u64>() {
-                    let f = File::new(false, Some(size));
-                    current_file.insert_child(l.next().unwrap(), f);
-                }
-            }
-        }
+type Data = File;
+impl DayImpl<Data> for Day<CURRENT_DAY> {
+    type Params = ();
 
-        (Self {}, root)
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        let mut root: File = File::new(true, None);
+        parse_tree(&mut root, input).map_err(|e| e.locate(CURRENT_DAY, input))?;
+        Ok((Self {}, root))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number(data.task_one())
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let max_space = 40000000;
         let space_to_clean = data.get_size() - max_space;
 
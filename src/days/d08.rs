@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{Answer, Day, DayImpl, InputError, ParseError, RunContext};
 
 const CURRENT_DAY: u8 = 8;
 
@@ -93,31 +93,35 @@ fn get_scenic_score(field: &Data, x: usize, y: usize) -> u64 {
 
 type Data = Vec<Vec<u32>>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test08.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(21), Answer::Number(8))
-    }
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        let mut field: Data = Vec::new();
+
+        for l in input.lines() {
+            let mut row = Vec::new();
+            for (i, c) in l.char_indices() {
+                match c.to_digit(10) {
+                    Some(h) => row.push(h),
+                    None => {
+                        return Err(InputError::new(&l[i..i + c.len_utf8()], "expected a tree height")
+                            .locate(CURRENT_DAY, input))
+                    }
+                }
+            }
+            if let Some(first) = field.first() {
+                if first.len() != row.len() {
+                    return Err(InputError::new(l, format!("expected {} trees", first.len()))
+                        .locate(CURRENT_DAY, input));
+                }
+            }
+            field.push(row);
+        }
 
-    fn init(input: &str) -> (Self, Data) {
-        (
-            Self {},
-            input
-                .lines()
-                .map(|v| {
-                    v.chars()
-                        .collect::<Vec<char>>()
-                        .iter()
-                        .map(|v| v.to_digit(10).unwrap())
-                        .collect()
-                })
-                .collect::<Vec<Vec<u32>>>(),
-        )
+        Ok((Self {}, field))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut visible = 0;
 
         let width = data[0].len();
@@ -139,7 +143,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(visible)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut best_score = 0;
 
         let width = data[0].len();
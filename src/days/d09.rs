@@ -1,6 +1,6 @@
 use std::ops::{Add, AddAssign, Sub};
 
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
@@ -31,15 +31,19 @@ impl Move {
     }
 }
 
-impl From<&str> for Move {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Move {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut s = value.split_ascii_whitespace();
-        match s.next().unwrap() {
-            "R" => Self::Right(s.next().unwrap().parse().unwrap()),
-            "L" => Self::Left(s.next().unwrap().parse().unwrap()),
-            "U" => Self::Up(s.next().unwrap().parse().unwrap()),
-            "D" => Self::Down(s.next().unwrap().parse().unwrap()),
-            _ => panic!("unexpected Move"),
+        let direction = token(s.next(), value, "a direction")?;
+        let steps = number(token(s.next(), value, "a number of steps")?)?;
+        match direction {
+            "R" => Ok(Self::Right(steps)),
+            "L" => Ok(Self::Left(steps)),
+            "U" => Ok(Self::Up(steps)),
+            "D" => Ok(Self::Down(steps)),
+            d => Err(InputError::new(d, "expected one of R, L, U or D")),
         }
     }
 }
@@ -167,19 +171,20 @@ const CURRENT_DAY: u8 = 9;
 
 type Data = Vec<Move>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test09.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(88), Answer::Number(36))
-    }
+    type Params = ();
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, input.lines().map(Move::from).collect())
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            input
+                .lines()
+                .map(Move::try_from)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut chain = Chain::new(2);
 
         for m in data {
@@ -189,7 +194,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(chain.get_visited_count() as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut chain = Chain::new(10);
 
         for m in data {
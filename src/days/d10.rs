@@ -1,4 +1,4 @@
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 10;
 
@@ -8,13 +8,15 @@ pub enum Command {
     AddX(i64),
 }
 
-impl From<&str> for Command {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Command {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut s = value.split_ascii_whitespace();
-        match s.next().unwrap() {
-            "noop" => Self::Noop,
-            "addx" => Self::AddX(s.next().unwrap().parse().unwrap()),
-            c => panic!("Unknown Command '{}'", c),
+        match token(s.next(), value, "an instruction")? {
+            "noop" => Ok(Self::Noop),
+            "addx" => Ok(Self::AddX(number(token(s.next(), value, "a value")?)?)),
+            c => Err(InputError::new(c, "expected `noop` or `addx`")),
         }
     }
 }
@@ -146,66 +148,26 @@ impl Computer {
 
 type Data = Computer;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test10.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (
-            Answer::Number(13140),
-            Answer::Bitmap(vec![
-                vec![
-                    true, true, false, false, true, true, false, false, true, true, false, false,
-                    true, true, false, false, true, true, false, false, true, true, false, false,
-                    true, true, false, false, true, true, false, false, true, true, false, false,
-                    true, true, false, false,
-                ],
-                vec![
-                    true, true, true, false, false, false, true, true, true, false, false, false,
-                    true, true, true, false, false, false, true, true, true, false, false, false,
-                    true, true, true, false, false, false, true, true, true, false, false, false,
-                    true, true, true, false,
-                ],
-                vec![
-                    true, true, true, true, false, false, false, false, true, true, true, true,
-                    false, false, false, false, true, true, true, true, false, false, false, false,
-                    true, true, true, true, false, false, false, false, true, true, true, true,
-                    false, false, false, false,
-                ],
-                vec![
-                    true, true, true, true, true, false, false, false, false, false, true, true,
-                    true, true, true, false, false, false, false, false, true, true, true, true,
-                    true, false, false, false, false, false, true, true, true, true, true, false,
-                    false, false, false, false,
-                ],
-                vec![
-                    true, true, true, true, true, true, false, false, false, false, false, false,
-                    true, true, true, true, true, true, false, false, false, false, false, false,
-                    true, true, true, true, true, true, false, false, false, false, false, false,
-                    true, true, true, true,
-                ],
-                vec![
-                    true, true, true, true, true, true, true, false, false, false, false, false,
-                    false, false, true, true, true, true, true, true, true, false, false, false,
-                    false, false, false, false, true, true, true, true, true, true, true, false,
-                    false, false, false, false,
-                ],
-            ]),
-        )
-    }
+    type Params = ();
 
-    fn init(input: &str) -> (Self, Data) {
-        (
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
             Self {},
-            Computer::new(input.lines().map(Command::from).collect()),
-        )
+            Computer::new(
+                input
+                    .lines()
+                    .map(Command::try_from)
+                    .collect::<ParseResult<Vec<Command>>>()
+                    .map_err(|e| e.locate(CURRENT_DAY, input))?,
+            ),
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number(data.get_signal_strength() as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Bitmap(data.draw_crt())
     }
 }
@@ -1,6 +1,6 @@
 use std::str::Lines;
 
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 11;
 
@@ -9,11 +9,13 @@ struct Item {
     worry_level: u64,
 }
 
-impl From<&str> for Item {
-    fn from(value: &str) -> Self {
-        Self {
-            worry_level: value.parse().unwrap(),
-        }
+impl<'a> TryFrom<&'a str> for Item {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        Ok(Self {
+            worry_level: number(value)?,
+        })
     }
 }
 
@@ -34,16 +36,18 @@ impl Operation {
     }
 }
 
-impl From<&str> for Operation {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Operation {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         if value.starts_with("new = old * old") {
-            Self::Square
-        } else if value.starts_with("new = old *") {
-            Self::Multiply(value.split_once("* ").unwrap().1.parse().unwrap())
-        } else if value.starts_with("new = old +") {
-            Self::Add(value.split_once("+ ").unwrap().1.parse().unwrap())
+            Ok(Self::Square)
+        } else if let Some(x) = value.strip_prefix("new = old * ") {
+            Ok(Self::Multiply(number(x)?))
+        } else if let Some(x) = value.strip_prefix("new = old + ") {
+            Ok(Self::Add(number(x)?))
         } else {
-            unreachable!("Unexpected input for Operation")
+            Err(InputError::new(value, "expected `new = old * ...` or `new = old + ...`"))
         }
     }
 }
@@ -57,7 +61,7 @@ struct Decision {
 
 impl Decision {
     fn decide(&self, item: &Item) -> usize {
-        if item.worry_level % self.divisible_by == 0 {
+        if item.worry_level.is_multiple_of(self.divisible_by) {
             self.true_monkey
         } else {
             self.false_monkey
@@ -66,33 +70,22 @@ impl Decision {
 }
 
 impl Decision {
-    fn from(lines: &mut Lines) -> Self {
-        Self {
-            divisible_by: lines
-                .next()
-                .unwrap()
-                .split_once("by ")
-                .unwrap()
-                .1
-                .parse()
-                .unwrap(),
-            true_monkey: lines
-                .next()
-                .unwrap()
-                .split_once("ey ")
-                .unwrap()
-                .1
-                .parse()
-                .unwrap(),
-            false_monkey: lines
-                .next()
-                .unwrap()
-                .split_once("ey ")
-                .unwrap()
-                .1
-                .parse()
-                .unwrap(),
+    /// Parses the three lines of a decision, `context` is the whole monkey.
+    fn parse<'a>(lines: &mut Lines<'a>, context: &'a str) -> ParseResult<'a, Self> {
+        let test = token(lines.next(), context, "a test")?;
+        let if_true = token(lines.next(), context, "a target for true")?;
+        let if_false = token(lines.next(), context, "a target for false")?;
+
+        let divisible_by = number(token(test.split_once("by ").map(|v| v.1), test, "`divisible by`")?)?;
+        if divisible_by == 0 {
+            return Err(InputError::new(test, "can't test for divisibility by zero"));
         }
+
+        Ok(Self {
+            divisible_by,
+            true_monkey: number(token(if_true.split_once("ey ").map(|v| v.1), if_true, "a monkey")?)?,
+            false_monkey: number(token(if_false.split_once("ey ").map(|v| v.1), if_false, "a monkey")?)?,
+        })
     }
 }
 
@@ -137,32 +130,36 @@ impl Monkey {
     }
 }
 
-impl From<&str> for Monkey {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Monkey {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut lines = value.lines();
-        if !lines.next().unwrap().starts_with("Monkey") {
-            panic!("Unexpected input.");
+        let header = token(lines.next(), value, "a monkey")?;
+        if !header.starts_with("Monkey") {
+            return Err(InputError::new(header, "expected `Monkey`"));
         }
 
         // Parse items
-        let items = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
+        let items_line = token(lines.next(), value, "starting items")?;
+        let items = token(items_line.split_once(": ").map(|v| v.1), items_line, "`: `")?
             .split(", ")
-            .map(Item::from)
-            .collect();
+            .map(Item::try_from)
+            .collect::<ParseResult<Vec<Item>>>()?;
 
-        let operation = Operation::from(lines.next().unwrap().split_once(": ").unwrap().1);
+        let operation_line = token(lines.next(), value, "an operation")?;
+        let operation = Operation::try_from(token(
+            operation_line.split_once(": ").map(|v| v.1),
+            operation_line,
+            "`: `",
+        )?)?;
 
-        Self {
+        Ok(Self {
             items,
             operation,
-            decision: Decision::from(&mut lines),
+            decision: Decision::parse(&mut lines, value)?,
             inspection_count: 0,
-        }
+        })
     }
 }
 
@@ -186,31 +183,42 @@ impl MonkeyTroop {
     }
 }
 
-impl From<&str> for MonkeyTroop {
-    fn from(value: &str) -> Self {
-        let monkeys: Vec<Monkey> = value.split("\n\n").map(Monkey::from).collect();
-        Self {
+impl<'a> TryFrom<&'a str> for MonkeyTroop {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        let blocks: Vec<&str> = value.split("\n\n").collect();
+        let mut monkeys: Vec<Monkey> = Vec::with_capacity(blocks.len());
+
+        for block in &blocks {
+            let monkey = Monkey::try_from(*block)?;
+            for target in [monkey.decision.true_monkey, monkey.decision.false_monkey] {
+                if target >= blocks.len() {
+                    return Err(InputError::new(block, format!("there is no monkey {}", target)));
+                }
+            }
+            monkeys.push(monkey);
+        }
+
+        Ok(Self {
             common: monkeys.iter().fold(1, |a, m| a * m.decision.divisible_by),
             monkeys,
-        }
+        })
     }
 }
 
 type Data = MonkeyTroop;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test11.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(10605), Answer::Number(2713310158))
-    }
+    type Params = ();
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, MonkeyTroop::from(input))
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            MonkeyTroop::try_from(input).map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         //println!("{:?}", data);
 
         for _ in 0..20 {
@@ -223,7 +231,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(counts[0] * counts[1])
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         for _ in 0..10000 {
             data.round(false);
         }
@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign},
 };
 
-use super::{Answer, Day, DayImpl};
+use super::{Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 12;
 
@@ -197,61 +197,62 @@ impl Map {
     }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
-        let mut start: Position = Position(0, 0);
-        let mut goal: Position = Position(0, 0);
+impl<'a> TryFrom<&'a str> for Map {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        let mut start = None;
+        let mut goal = None;
 
         let mut heights = Vec::new();
         for (y, line) in value.lines().enumerate() {
             let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                if c.is_uppercase() {
-                    match c {
-                        'S' => {
-                            start = Position(x as i32, y as i32);
-                            row.push(0)
-                        }
-                        'E' => {
-                            goal = Position(x as i32, y as i32);
-                            row.push(25)
-                        }
-                        _ => unreachable!("Unexpected Input"),
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                match c {
+                    'S' => {
+                        start = Some(Position(x as i32, y as i32));
+                        row.push(0)
+                    }
+                    'E' => {
+                        goal = Some(Position(x as i32, y as i32));
+                        row.push(25)
+                    }
+                    'a'..='z' => row.push(c as u8 - 97),
+                    _ => {
+                        return Err(InputError::new(
+                            &line[i..i + c.len_utf8()],
+                            "expected a height between `a` and `z`, `S` or `E`",
+                        ))
                     }
-                } else {
-                    row.push(c as u8 - 97);
                 }
             }
             heights.push(row);
         }
 
-        Self {
-            start,
-            goal,
+        Ok(Self {
+            start: start.ok_or_else(|| InputError::missing(value, "a start position `S`"))?,
+            goal: goal.ok_or_else(|| InputError::missing(value, "a goal position `E`"))?,
             heights,
-        }
+        })
     }
 }
 
 type Data = Map;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test12.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(31), Answer::Number(29))
-    }
+    type Params = ();
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, Map::from(input))
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            Map::try_from(input).map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number((data.find_path(data.start).unwrap().len() - 1) as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number((data.search_from_end(data.goal, 0).unwrap().len() - 1) as u64)
     }
 }
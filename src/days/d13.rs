@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, str::Chars};
 
-use super::{Answer, Day, DayImpl};
+use super::{number, token, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 13;
 
@@ -53,63 +53,79 @@ impl Item {
     }
 
     // Disgusting parser, please don't blame, at this point some other stuff came in the way and I don't have the time or motivation to do stuff nicely.
-    fn parse_list(chars: &mut Chars) -> Self {
+    fn parse_list<'a>(chars: &mut Chars<'a>, nested: bool) -> ParseResult<'a, Self> {
         let mut items = Vec::new();
 
-        while let Some(c) = chars.next() {
+        loop {
+            let rest = chars.as_str();
+            let c = match chars.next() {
+                Some(c) => c,
+                None if nested => return Err(InputError::missing(rest, "`]`")),
+                None => break,
+            };
             match c {
-                '[' => items.push(Self::parse_list(chars)),
+                '[' => items.push(Self::parse_list(chars, true)?),
                 ']' => break,
                 ',' => {}
                 c => {
                     if c.is_numeric() {
-                        let (value, exit) = Self::parse_value(String::from(c), chars);
+                        let (value, exit) = Self::parse_value(rest, chars)?;
                         items.push(value);
                         if exit {
                             break;
                         }
                     } else {
-                        panic!("unexpected input");
+                        return Err(InputError::new(
+                            &rest[..c.len_utf8()],
+                            "expected `[`, `]`, `,` or a number",
+                        ));
                     }
                 }
             }
         }
 
-        Self::List(items)
+        Ok(Self::List(items))
     }
 
-    fn parse_value(mut s: String, chars: &mut Chars) -> (Self, bool) {
+    /// `start` is the remaining input, beginning with the first digit of the value.
+    fn parse_value<'a>(start: &'a str, chars: &mut Chars<'a>) -> ParseResult<'a, (Self, bool)> {
         for c in chars.by_ref() {
-            if c.is_numeric() {
-                s.push(c)
-            } else {
-                return (Self::Value(s.parse().unwrap()), c == ']');
+            if !c.is_numeric() {
+                let end = start.len() - chars.as_str().len() - c.len_utf8();
+                let value = Self::Value(number(&start[..end])?);
+                return match c {
+                    ']' => Ok((value, true)),
+                    ',' => Ok((value, false)),
+                    _ => Err(InputError::new(&start[end..end + c.len_utf8()], "expected `,` or `]`")),
+                };
             }
         }
 
-        unreachable!("Unexpected input.")
+        Err(InputError::missing(start, "`]`"))
     }
 }
 
-impl From<&str> for Item {
-    fn from(value: &str) -> Self {
-        Self::parse_list(&mut value.chars())
+impl<'a> TryFrom<&'a str> for Item {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        Self::parse_list(&mut value.chars(), false)
     }
 }
 
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.compare(other) {
-            ComparisonResult::Correct => Some(Ordering::Less),
-            ComparisonResult::Wrong => Some(Ordering::Greater),
-            ComparisonResult::Continue => None, //Yes, this will fail when used with `Ord`, but it should never be the case and I don't care rn
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Item {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match self.compare(other) {
+            ComparisonResult::Correct => Ordering::Less,
+            ComparisonResult::Wrong => Ordering::Greater,
+            ComparisonResult::Continue => Ordering::Equal,
+        }
     }
 }
 
@@ -122,31 +138,34 @@ impl Pair {
     }
 }
 
-impl From<&str> for Pair {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Pair {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut lines = value.lines();
-        Self(
-            Item::from(lines.next().unwrap()),
-            Item::from(lines.next().unwrap()),
-        )
+        Ok(Self(
+            Item::try_from(token(lines.next(), value, "a packet")?)?,
+            Item::try_from(token(lines.next(), value, "a second packet")?)?,
+        ))
     }
 }
 
 type Data = Vec<Pair>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test13.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(13), Answer::Number(140))
-    }
-
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, input.split("\n\n").map(Pair::from).collect())
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            input
+                .split("\n\n")
+                .map(Pair::try_from)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut out = 0;
 
         for (i, p) in data.iter().enumerate() {
@@ -158,7 +177,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(out as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let a_package = Item::List(vec![Item::List(vec![Item::Value(2)])]);
         let b_package = Item::List(vec![Item::List(vec![Item::Value(6)])]);
 
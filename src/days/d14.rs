@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::BTreeMap};
 
-use super::{Answer, Day, DayImpl};
+use super::{number, param, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 14;
 
@@ -43,10 +43,14 @@ impl Position {
     }
 }
 
-impl From<&str> for Position {
-    fn from(value: &str) -> Self {
-        let s = value.split_once(',').unwrap();
-        Self(s.0.parse().unwrap(), s.1.parse().unwrap())
+impl<'a> TryFrom<&'a str> for Position {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        let s = value
+            .split_once(',')
+            .ok_or_else(|| InputError::missing(value, "`,`"))?;
+        Ok(Self(number(s.0)?, number(s.1)?))
     }
 }
 
@@ -67,13 +71,14 @@ struct Line {
 }
 
 impl Line {
-    fn new(a: Position, b: Position) -> Self {
-        Self {
+    /// Returns `None` if the line isn't horizontal or vertical.
+    fn new(a: Position, b: Position) -> Option<Self> {
+        Some(Self {
             a,
             index: 0,
-            dir: a.get_direction_to(&b).unwrap(),
+            dir: a.get_direction_to(&b)?,
             max_index: a.get_block_distance(&b) + 1,
-        }
+        })
     }
 }
 
@@ -96,13 +101,14 @@ pub struct Map {
     tiles: BTreeMap<Position, Tile>,
     deepest_stone_y: usize,
     floor_enabled: bool,
+    source: Position,
 }
 
 impl Map {
     /// Spawns and simulates a sand tile.
     /// Returns true when sand rests and false when sand falls into infinity.
     fn spawn_sand(&mut self) -> bool {
-        let mut pos = Position(500, 0);
+        let mut pos = self.source;
         let limit = if self.floor_enabled {
             self.deepest_stone_y + 2
         } else {
@@ -110,8 +116,12 @@ impl Map {
         };
 
         while pos.1 < limit {
+            let left = pos
+                .0
+                .checked_sub(1)
+                .expect("the sand fell past x=0, use a source further to the right");
             match (
-                self.is_free(&Position(pos.0 - 1, pos.1 + 1)),
+                self.is_free(&Position(left, pos.1 + 1)),
                 self.is_free(&Position(pos.0, pos.1 + 1)),
                 self.is_free(&Position(pos.0 + 1, pos.1 + 1)),
             ) {
@@ -133,7 +143,7 @@ impl Map {
                 _ => {
                     //println!("Came to rest at {:?}", pos);
                     self.tiles.insert(pos, Tile::Sand);
-                    if self.floor_enabled && pos == Position(500, 0) {
+                    if self.floor_enabled && pos == self.source {
                         return false;
                     }
                     return true;
@@ -149,18 +159,20 @@ impl Map {
     }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Map {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut tiles = BTreeMap::new();
         let mut deepest_stone_y = 0;
 
         for l in value.lines() {
             let mut prev = None;
             for p in l.split(" -> ") {
-                let pos = Position::from(p);
+                let pos = Position::try_from(p)?;
 
                 if let Some(prev_pos) = prev {
-                    let l = Line::new(prev_pos, pos);
+                    let l = Line::new(prev_pos, pos).ok_or_else(|| InputError::new(p, "expected a horizontal or vertical line"))?;
                     for p in l {
                         tiles.insert(p, Tile::Stone);
                     }
@@ -173,29 +185,62 @@ impl From<&str> for Map {
             }
         }
 
-        Self {
+        Ok(Self {
             tiles,
             deepest_stone_y,
             floor_enabled: false,
-        }
+            source: Position(500, 0),
+        })
     }
 }
 
-type Data = Map;
-impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test14.txt"))
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Where the sand is poured in.
+    source: Position,
+}
+
+impl super::Params for Params {
+    fn example() -> Self {
+        Self::real()
     }
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(24), Answer::Number(93))
+    fn real() -> Self {
+        Self {
+            source: Position(500, 0),
+        }
     }
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, Map::from(input))
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "source" => {
+                let (x, y) = value
+                    .split_once(',')
+                    .ok_or_else(|| format!("expected `x,y`, found `{}`", value))?;
+                let source = Position(param(x)?, param(y)?);
+                // Sand falls diagonally to the left of the source, which needs room.
+                if source.0 == 0 {
+                    return Err("the source has to be right of x=0".to_owned());
+                }
+                self.source = source;
+            }
+            _ => return Err("unknown parameter, expected `source`".to_owned()),
+        }
+        Ok(())
+    }
+}
+
+type Data = Map;
+impl DayImpl<Data> for Day<CURRENT_DAY> {
+    type Params = Params;
+
+    fn init(input: &str, ctx: &RunContext<Params>) -> Result<(Self, Data), ParseError> {
+        let mut map = Map::try_from(input).map_err(|e| e.locate(CURRENT_DAY, input))?;
+        map.source = ctx.params().source;
+        Ok((Self {}, map))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<Params>) -> Answer {
         let mut i = 0;
         while data.spawn_sand() {
             i += 1;
@@ -203,7 +248,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(i)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<Params>) -> Answer {
         let mut i = 1;
 
         data.floor_enabled = true;
@@ -1,9 +1,39 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use super::{Answer, Day, DayImpl};
+use super::{number, param, Answer, CancelToken, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 15;
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// The row checked in part 1.
+    row: i64,
+    /// The highest x and y coordinate the distress beacon can have in part 2.
+    max: i64,
+}
+
+impl super::Params for Params {
+    fn example() -> Self {
+        Self { row: 10, max: 20 }
+    }
+
+    fn real() -> Self {
+        Self {
+            row: 2000000,
+            max: 4000000,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "row" => self.row = param(value)?,
+            "max" => self.max = param(value)?,
+            _ => return Err("unknown parameter, expected `row` or `max`".to_owned()),
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 struct Position(i64, i64);
 
@@ -47,13 +77,19 @@ impl Position {
     }
 }
 
-impl From<&str> for Position {
-    fn from(value: &str) -> Self {
-        let (_, s) = value.split_at(2);
-        let (x_str, s) = s.split_once(',').unwrap();
-        let (_, y_str) = s.split_once('=').unwrap();
+impl<'a> TryFrom<&'a str> for Position {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        let s = value
+            .strip_prefix("x=")
+            .ok_or_else(|| InputError::new(value, "expected `x=`"))?;
+        let (x_str, s) = s
+            .split_once(',')
+            .ok_or_else(|| InputError::missing(s, "`,`"))?;
+        let (_, y_str) = s.split_once('=').ok_or_else(|| InputError::missing(s, "`y=`"))?;
 
-        Self(x_str.parse().unwrap(), y_str.parse().unwrap())
+        Ok(Self(number(x_str)?, number(y_str)?))
     }
 }
 
@@ -65,6 +101,8 @@ struct Sensor {
 #[derive(Debug, Clone)]
 pub struct Map {
     sensors: BTreeMap<Position, Sensor>,
+    beacons: BTreeSet<Position>,
+    /// The lowest and highest x coordinate any sensor reaches, both inclusive.
     x_limits: (i64, i64),
 }
 
@@ -79,13 +117,18 @@ impl Map {
         true
     }
 
-    fn find_free_space(&self) -> Option<Position> {
-        for (sensor_pos, sensor) in &self.sensors {
+    /// `None` if there is no free space, or if it was cancelled.
+    fn find_free_space(&self, max: i64, ctx: &RunContext<Params>) -> Option<Position> {
+        for (i, (sensor_pos, sensor)) in self.sensors.iter().enumerate() {
+            if ctx.is_cancelled() {
+                return None;
+            }
+            ctx.progress(i as u64, self.sensors.len() as u64);
             for p in sensor_pos.get_border_positions(sensor.beacon_distance) {
                 if p.0 > 0
                     && p.1 > 0
-                    && p.0 <= 4000000
-                    && p.1 <= 4000000
+                    && p.0 <= max
+                    && p.1 <= max
                     && self.can_contain_beacon(&p)
                 {
                     return Some(p);
@@ -95,66 +138,90 @@ impl Map {
         None
     }
 
-    fn check_line(&self, y: i64) -> u64 {
+    /// The positions in row `y` which are covered by a sensor, but aren't a known beacon.
+    fn check_line(&self, y: i64, cancel: &CancelToken) -> u64 {
         let mut c = 0;
-        for x in self.x_limits.0..self.x_limits.1 {
-            if !self.can_contain_beacon(&Position(x, y)) {
+        for x in self.x_limits.0..=self.x_limits.1 {
+            if cancel.is_cancelled() {
+                return 0;
+            }
+            let p = Position(x, y);
+            if !self.can_contain_beacon(&p) && !self.beacons.contains(&p) {
                 c += 1;
             }
         }
 
-        c - 1 //I don't know why its one too many... Maybe I'll search for the source later, but for now this works.
+        c
     }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
+impl<'a> TryFrom<&'a str> for Map {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         let mut sensors = BTreeMap::new();
+        let mut beacons = BTreeSet::new();
         let mut x_limits = (0, 0);
 
-        for l in value.lines() {
-            let (_, l) = l.split_at(10);
-            let (sensor_pos_str, l) = l.split_once(':').unwrap();
-            let (_, beacon_pos_str) = l.split_once("at ").unwrap();
-
-            let sensor_pos = Position::from(sensor_pos_str);
-            let beacon_pos = Position::from(beacon_pos_str);
+        for line in value.lines() {
+            let l = line
+                .strip_prefix("Sensor at ")
+                .ok_or_else(|| InputError::new(line, "expected `Sensor at`"))?;
+            let (sensor_pos_str, l) = l
+                .split_once(':')
+                .ok_or_else(|| InputError::missing(l, "`:`"))?;
+            let (_, beacon_pos_str) = l
+                .split_once("at ")
+                .ok_or_else(|| InputError::missing(l, "`closest beacon is at`"))?;
+
+            let sensor_pos = Position::try_from(sensor_pos_str)?;
+            let beacon_pos = Position::try_from(beacon_pos_str)?;
             let distance = sensor_pos.get_block_distance(&beacon_pos);
 
-            x_limits.0 = x_limits.0.min(sensor_pos.1 - distance as i64);
-            x_limits.1 = x_limits.1.max(sensor_pos.1 + distance as i64);
+            x_limits.0 = x_limits.0.min(sensor_pos.0 - distance as i64);
+            x_limits.1 = x_limits.1.max(sensor_pos.0 + distance as i64);
 
             let sensor = Sensor {
                 beacon_distance: sensor_pos.get_block_distance(&beacon_pos),
             };
 
             sensors.insert(sensor_pos, sensor);
+            beacons.insert(beacon_pos);
         }
 
-        Self { sensors, x_limits }
+        Ok(Self {
+            sensors,
+            beacons,
+            x_limits,
+        })
     }
 }
 
 type Data = Map;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test15.txt"))
-    }
+    type Params = Params;
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(5068327), Answer::Number(11016575214126))
+    fn init(input: &str, _ctx: &RunContext<Params>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            Map::try_from(input).map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, Map::from(input))
+    fn one(&self, map: &mut Data, ctx: &RunContext<Params>) -> Answer {
+        let row = ctx.params().row;
+        debug!("checking row {}", row);
+        Answer::Number(map.check_line(row, ctx.cancel_token()))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
-        Answer::Number(data.check_line(10))
-    }
-
-    fn two(&self, data: &mut Data) -> Answer {
-        let p = data.find_free_space().unwrap();
-        Answer::Number((p.0 * 4000000 + p.1) as u64)
+    fn two(&self, map: &mut Data, ctx: &RunContext<Params>) -> Answer {
+        let Some(p) = map.find_free_space(ctx.params().max, ctx) else {
+            if ctx.is_cancelled() {
+                return Answer::Number(0);
+            }
+            panic!("there is no space for the distress beacon");
+        };
+        debug!("distress beacon at x={}, y={}", p.0, p.1);
+        Answer::from(p.0 * 4000000 + p.1)
     }
 }
@@ -4,7 +4,7 @@ use std::{
     ops::{Add, Sub},
 };
 
-use super::{Answer, Day, DayImpl};
+use super::{param, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
@@ -13,12 +13,14 @@ pub enum Direction {
     Down,
 }
 
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
+impl<'a> TryFrom<&'a str> for Direction {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
         match value {
-            '<' => Self::Left,
-            '>' => Self::Right,
-            _ => unreachable!("Unexpected input value."),
+            "<" => Ok(Self::Left),
+            ">" => Ok(Self::Right),
+            _ => Err(InputError::new(value, "expected `<` or `>`")),
         }
     }
 }
@@ -283,29 +285,73 @@ impl Chamber {
 
 const CURRENT_DAY: u8 = 17;
 
-type Data = Vec<Direction>;
-impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test17.txt"))
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// The number of rocks dropped in part 1.
+    rocks: usize,
+    /// The width of the chamber.
+    width: u8,
+}
+
+impl super::Params for Params {
+    fn example() -> Self {
+        Self::real()
+    }
+
+    fn real() -> Self {
+        Self {
+            rocks: 2022,
+            width: 7,
+        }
     }
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(3068), Answer::Number(0))
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "rocks" => self.rocks = param(value)?,
+            "width" => {
+                let width = param(value)?;
+                // Rocks appear 2 units from the left wall and the widest one is 4 units wide.
+                if width < 6 {
+                    return Err(format!("the chamber has to be at least 6 wide, not {}", width));
+                }
+                self.width = width;
+            }
+            _ => return Err("unknown parameter, expected `rocks` or `width`".to_owned()),
+        }
+        Ok(())
     }
+}
 
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, input.chars().map(Direction::from).collect())
+type Data = Vec<Direction>;
+impl DayImpl<Data> for Day<CURRENT_DAY> {
+    type Params = Params;
+
+    fn init(input: &str, _ctx: &RunContext<Params>) -> Result<(Self, Data), ParseError> {
+        let jets = input
+            .char_indices()
+            .map(|(i, c)| Direction::try_from(&input[i..i + c.len_utf8()]))
+            .collect::<ParseResult<Vec<Direction>>>()
+            .map_err(|e| e.locate(CURRENT_DAY, input))?;
+        if jets.is_empty() {
+            return Err(InputError::missing(input, "a jet pattern like `>>><<`")
+                .locate(CURRENT_DAY, input));
+        }
+        Ok((Self {}, jets))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
-        let mut chamber = Chamber::new(7, data.clone());
-        for _ in 0..2022 {
+    fn one(&self, wind: &mut Data, ctx: &RunContext<Params>) -> Answer {
+        let params = ctx.params();
+        let mut chamber = Chamber::new(params.width, wind.clone());
+        for _ in 0..params.rocks {
+            if ctx.is_cancelled() {
+                break;
+            }
             chamber.spawn_rock();
         }
         Answer::Number(chamber.stack_height as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, _data: &mut Data, _ctx: &RunContext<Params>) -> Answer {
         Answer::Number(0)
     }
 }
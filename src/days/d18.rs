@@ -3,7 +3,7 @@ use std::{
     ops::{Add, Sub},
 };
 
-use super::{Answer, Day, DayImpl};
+use super::{number, Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 18;
 
@@ -38,15 +38,19 @@ impl Sub for Position {
     }
 }
 
-impl From<&str> for Position {
-    fn from(value: &str) -> Self {
-        let (x, s) = value.split_once(',').unwrap();
-        let (y, z) = s.split_once(',').unwrap();
-        Self {
-            x: x.parse().unwrap(),
-            y: y.parse().unwrap(),
-            z: z.parse().unwrap(),
-        }
+impl<'a> TryFrom<&'a str> for Position {
+    type Error = InputError<'a>;
+
+    fn try_from(value: &'a str) -> ParseResult<'a, Self> {
+        let (x, s) = value
+            .split_once(',')
+            .ok_or_else(|| InputError::missing(value, "`,`"))?;
+        let (y, z) = s.split_once(',').ok_or_else(|| InputError::missing(s, "`,`"))?;
+        Ok(Self {
+            x: number(x)?,
+            y: number(y)?,
+            z: number(z)?,
+        })
     }
 }
 
@@ -126,19 +130,20 @@ fn check_outside_connection(
 
 type Data = HashSet<Position>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test18.txt"))
-    }
-
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(64), Answer::Number(58))
-    }
-
-    fn init(input: &str) -> (Self, Data) {
-        (Self {}, input.lines().map(Position::from).collect())
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            input
+                .lines()
+                .map(Position::try_from)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut n = 0;
 
         for p in data.iter() {
@@ -152,7 +157,7 @@ impl DayImpl<Data> for Day<CURRENT_DAY> {
         Answer::Number(n)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         let mut n = 0;
         let mut max = Position { x: 0, y: 0, z: 0 };
 
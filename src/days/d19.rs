@@ -1,32 +1,31 @@
-use super::{Answer, Day, DayImpl};
+use super::{number, Answer, Day, DayImpl, ParseError, ParseResult, RunContext};
 
 const CURRENT_DAY: u8 = 19;
 
 type Data = Vec<u64>;
 impl DayImpl<Data> for Day<CURRENT_DAY> {
-    fn init_test() -> (Self, Data) {
-        Self::init(include_str!("test_inputs/test19.txt"))
-    }
+    type Params = ();
 
-    fn expected_results() -> (Answer, Answer) {
-        (Answer::Number(0), Answer::Number(0))
+    fn implemented() -> bool {
+        false
     }
 
-    fn init(input: &str) -> (Self, Data) {
-        (
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
             Self {},
             input
                 .lines()
-                .map(|v| v.parse::<u64>().expect("error while parsing input."))
-                .collect(),
-        )
+                .map(number)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
     }
 
-    fn one(&self, data: &mut Data) -> Answer {
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number(data.len() as u64)
     }
 
-    fn two(&self, data: &mut Data) -> Answer {
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
         Answer::Number(data.len() as u64)
     }
 }
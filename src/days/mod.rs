@@ -1,4 +1,16 @@
-use aoc_macro::mod_days;
+use crate::context::{CancelToken, RunContext};
+use crate::log;
+use crate::memory::{self, AllocStats};
+use crate::ocr;
+use crate::{Part, RunOptions};
+use aoc_macro::{mod_days, register_days};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
 use std::time::{Duration, Instant};
 
 // Thanks to andi-makes with his AoC project https://github.com/andi-makes/aoc2021,
@@ -6,14 +18,58 @@ use std::time::{Duration, Instant};
 
 pub struct Day<const DAY: u8>;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Answer {
     Number(u64),
+    Signed(i64),
+    /// For answers which don't fit into 64 bits.
+    Wide(i128),
+    /// For answers which don't even fit into an `i128`.
+    WideUnsigned(u128),
     String(String),
     Bitmap(Vec<Vec<bool>>),
+    /// A picture made of characters, like a map, which isn't read as letters.
+    Grid(Vec<Vec<char>>),
 }
 
 impl Answer {
+    /// The answer as it would be typed into the website, bitmaps are read using OCR.
+    /// `None` for grids and if the bitmap contains anything but known letters.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Self::Number(n) => Some(n.to_string()),
+            Self::Signed(n) => Some(n.to_string()),
+            Self::Wide(n) => Some(n.to_string()),
+            Self::WideUnsigned(n) => Some(n.to_string()),
+            Self::String(s) => Some(s.clone()),
+            Self::Bitmap(bm) => ocr::decode(bm),
+            Self::Grid(_) => None,
+        }
+    }
+
+    /// The value of numeric answers, regardless of the variant they are stored in.
+    /// Strings count if they are written exactly like the number would be, like `-12` but not `012`.
+    /// `None` if the value doesn't fit into an `i128`.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Self::Number(n) => Some(*n as i128),
+            Self::Signed(n) => Some(*n as i128),
+            Self::Wide(n) => Some(*n),
+            Self::WideUnsigned(n) => i128::try_from(*n).ok(),
+            Self::String(s) => s.parse().ok().filter(|n: &i128| n.to_string() == *s),
+            _ => None,
+        }
+    }
+
+    /// Like [`Self::as_i128`], `None` for negative values.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Self::WideUnsigned(n) => Some(*n),
+            Self::String(s) => s.parse().ok().filter(|n: &u128| n.to_string() == *s),
+            a => a.as_i128().and_then(|n| u128::try_from(n).ok()),
+        }
+    }
+
     fn append_per_line(str: String, prefix: &str) -> String {
         str.lines()
             .map(|v| prefix.to_owned() + v + "\n")
@@ -33,7 +89,7 @@ impl Answer {
         false
     }
 
-    fn minify_bitmap(bm: &Vec<Vec<bool>>) -> String {
+    fn minify_bitmap(bm: &[Vec<bool>]) -> String {
         let height = bm.len();
         let width = bm[0].len();
 
@@ -80,168 +136,747 @@ impl std::fmt::Display for Answer {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Self::Number(n) => write!(f, "{}", n),
+            Self::Signed(n) => write!(f, "{}", n),
+            Self::Wide(n) => write!(f, "{}", n),
+            Self::WideUnsigned(n) => write!(f, "{}", n),
             Self::String(s) => write!(f, "{}", s),
+            Self::Grid(grid) => {
+                let rows: Vec<String> = grid.iter().map(|row| row.iter().collect()).collect();
+                writeln!(f)?;
+                write!(f, "{}", Self::append_per_line(rows.join("\n"), "\t\t"))
+            }
             Self::Bitmap(bm) => {
-                writeln!(f).unwrap();
+                if let Some(text) = ocr::decode(bm) {
+                    write!(f, "{}", text)?;
+                }
+                writeln!(f)?;
                 write!(f, "{}", Self::minify_bitmap(bm))
             }
         }
     }
 }
 
+/// Numbers are equal if they have the same value, whichever variant they are stored in,
+/// also to strings containing the number (see [`Answer::as_i128`]).
+/// Bitmaps are equal to strings containing the letters they show,
+/// and to grids drawing them with `#` and `.`.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.as_i128(), other.as_i128()) {
+            return a == b;
+        }
+        if let (Some(a), Some(b)) = (self.as_u128(), other.as_u128()) {
+            return a == b;
+        }
+        match (self, other) {
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Bitmap(a), Self::Bitmap(b)) => a == b,
+            (Self::Grid(a), Self::Grid(b)) => a == b,
+            (Self::Bitmap(bm), Self::String(s)) | (Self::String(s), Self::Bitmap(bm)) => {
+                ocr::decode(bm).as_ref() == Some(s)
+            }
+            (Self::Bitmap(bm), Self::Grid(grid)) | (Self::Grid(grid), Self::Bitmap(bm)) => {
+                bm.len() == grid.len()
+                    && bm.iter().zip(grid).all(|(a, b)| {
+                        a.len() == b.len()
+                            && a.iter()
+                                .zip(b)
+                                .all(|(p, c)| *c == if *p { '#' } else { '.' })
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    /// Parse an answer as written in an example's `.expected` file.
+    /// Numbers use the smallest variant they fit into, multiple lines consisting only
+    /// of `#` and `.` are read as a bitmap and any other multiple lines as a grid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("empty answer".to_owned());
+        }
+        if let Ok(n) = s.parse() {
+            return Ok(Self::Number(n));
+        }
+        if let Ok(n) = s.parse() {
+            return Ok(Self::Signed(n));
+        }
+        if let Ok(n) = s.parse() {
+            return Ok(Self::Wide(n));
+        }
+        if let Ok(n) = s.parse() {
+            return Ok(Self::WideUnsigned(n));
+        }
+        if s.contains('\n') && s.chars().all(|c| matches!(c, '#' | '.' | '\n')) {
+            return Ok(Self::Bitmap(
+                s.lines()
+                    .map(|l| l.chars().map(|c| c == '#').collect())
+                    .collect(),
+            ));
+        }
+        if s.contains('\n') {
+            return Ok(Self::Grid(s.lines().map(|l| l.chars().collect()).collect()));
+        }
+        Ok(Self::String(s.to_owned()))
+    }
+}
+
 impl From<u64> for Answer {
     fn from(n: u64) -> Self {
         Self::Number(n)
     }
 }
 
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Signed(n)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Self::Wide(n)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Self::WideUnsigned(n)
+    }
+}
+
 impl From<String> for Answer {
     fn from(s: String) -> Self {
         Self::String(s)
     }
 }
 
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(grid: Vec<Vec<char>>) -> Self {
+        Self::Grid(grid)
+    }
+}
+
+/// An error encountered while parsing the input of a day,
+/// located by line and column (both starting at 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.reason
+        )?;
+        if self.text.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A parsing error which still borrows the offending part of the input.
+/// Days return this from their parsers and turn it into a [`ParseError`]
+/// using [`InputError::locate`] once the whole input is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError<'a> {
+    text: &'a str,
+    reason: String,
+}
+
+impl<'a> InputError<'a> {
+    /// `text` has to be a slice of the input, otherwise it can't be located.
+    pub fn new(text: &'a str, reason: impl Into<String>) -> Self {
+        Self {
+            text,
+            reason: reason.into(),
+        }
+    }
+
+    /// Error for something missing at the end of `context`.
+    pub fn missing(context: &'a str, what: &str) -> Self {
+        Self::new(&context[context.len()..], format!("expected {}", what))
+    }
+
+    /// Find the offending text within `input` and build the final error.
+    pub fn locate(self, day: u8, input: &str) -> ParseError {
+        let offset = (self.text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|o| *o <= input.len())
+            .unwrap_or(input.len());
+        let before = &input[..offset];
+
+        ParseError {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().unwrap_or("").chars().count() + 1,
+            text: self
+                .text
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(40)
+                .collect(),
+            reason: self.reason,
+        }
+    }
+}
+
+pub type ParseResult<'a, T> = Result<T, InputError<'a>>;
+
+/// Parse `text` as a number.
+pub fn number<T: FromStr>(text: &str) -> ParseResult<'_, T> {
+    text.parse()
+        .map_err(|_| InputError::new(text, "expected a number"))
+}
+
+/// Unwrap a token which was taken from `context`.
+pub fn token<'a>(token: Option<&'a str>, context: &'a str, what: &str) -> ParseResult<'a, &'a str> {
+    token.ok_or_else(|| InputError::missing(context, what))
+}
+
+/// Parameters which are not part of the input, but stated in the puzzle text,
+/// like the row to check in day 15. They often differ between the examples
+/// and the real input, so each day provides defaults for both.
+pub trait Params: Clone + Send + Sync + Sized + 'static {
+    /// Defaults for the examples of the puzzle description.
+    fn example() -> Self;
+
+    /// Defaults for the real input.
+    fn real() -> Self;
+
+    /// Override a single parameter, for example from `--param key=value`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+impl Params for () {
+    fn example() -> Self {}
+
+    fn real() -> Self {}
+
+    fn set(&mut self, _key: &str, _value: &str) -> Result<(), String> {
+        Err("this day has no parameters".to_owned())
+    }
+}
+
+/// Parse the value of a parameter.
+pub fn param<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value `{}`", value))
+}
+
+/// Whether the input is one of the examples, used to pick the default [`Params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+/// An invalid or unknown parameter given to a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub day: u8,
+    pub key: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, parameter `{}`: {}",
+            self.day, self.key, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// A panic of a day, caught so the other days can still be run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    /// Where it panicked, like `src/days/d12.rs:80:45`.
+    pub location: Option<String>,
+}
+
+impl std::fmt::Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything that can go wrong while running a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input couldn't be parsed.
+    Parse(ParseError),
+    /// A parameter was unknown or had an invalid value.
+    Param(ParamError),
+    /// The input couldn't be read, downloaded or loaded from the examples.
+    Input(String),
+    /// A part took longer than the time limit, and was told to stop.
+    Timeout(Duration),
+    /// Parsing or a part panicked.
+    Panic(Panic),
+    /// The day doesn't exist or has no solution yet.
+    NotImplemented(u8),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "Invalid input: {}", err),
+            Self::Param(err) => write!(f, "Invalid parameter: {}", err),
+            Self::Input(err) => write!(f, "Missing input: {}", err),
+            Self::Timeout(limit) => write!(f, "Timed out after {:?}", limit),
+            Self::Panic(panic) => write!(f, "Panicked: {}", panic),
+            Self::NotImplemented(day) => write!(f, "Day {} isn't implemented", day),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<ParamError> for Error {
+    fn from(err: ParamError) -> Self {
+        Self::Param(err)
+    }
+}
+
+/// Build the parameters of a day from its defaults for `kind`,
+/// and apply the `overrides` in order.
+pub fn build_params<P: Params>(
+    day: u8,
+    kind: InputKind,
+    overrides: &[(String, String)],
+) -> Result<P, ParamError> {
+    let mut params = match kind {
+        InputKind::Example => P::example(),
+        InputKind::Real => P::real(),
+    };
+    for (key, value) in overrides {
+        params.set(key, value).map_err(|reason| ParamError {
+            day,
+            key: key.clone(),
+            reason,
+        })?;
+    }
+    Ok(params)
+}
+
+thread_local! {
+    /// Whether a day runs on this thread, its panics are recorded instead of printed.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+/// Run a day, turning a panic into an error instead of ending the program.
+fn isolated<R>(f: impl FnOnce() -> R) -> Result<R, Error> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !ISOLATED.with(|i| i.get()) {
+                return default(info);
+            }
+            let panic = Panic {
+                message: panic_message(info.payload()),
+                location: info.location().map(|l| l.to_string()),
+            };
+            LAST_PANIC.with(|p| *p.borrow_mut() = Some(panic));
+        }));
+    });
+
+    let outer = ISOLATED.with(|i| i.replace(true));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED.with(|i| i.set(outer));
+    result.map_err(|payload| {
+        Error::Panic(
+            LAST_PANIC
+                .with(|p| p.borrow_mut().take())
+                .unwrap_or_else(|| Panic {
+                    message: panic_message(&*payload),
+                    location: None,
+                }),
+        )
+    })
+}
+
+/// The result of a part, the time it took and its allocations.
+/// The error is a timeout or a panic.
+pub type PartResult = (Result<Answer, Error>, Duration, Option<AllocStats>);
+
+/// Compute a part, on its own thread if there is a time limit. Once the limit has passed,
+/// the part is cancelled using `token` and left behind, it ends as soon as it checks it.
+fn limited(
+    timeout: Option<Duration>,
+    token: CancelToken,
+    part: impl FnOnce() -> (Answer, Duration) + Send + 'static,
+) -> PartResult {
+    let run = move || {
+        let start = Instant::now();
+        match memory::measure(|| isolated(part)) {
+            (Ok((answer, time)), memory) => (Ok(answer), time, memory),
+            (Err(err), memory) => (Err(err), start.elapsed(), memory),
+        }
+    };
+    let Some(timeout) = timeout else {
+        return run();
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(run());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            (Err(Error::Timeout(timeout)), timeout, None)
+        }
+        Err(RecvTimeoutError::Disconnected) => unreachable!("the part ended without an answer"),
+    }
+}
+
 pub trait DayImpl<T>
 where
     T: Clone,
 {
-    /// Parses the test input.
-    fn init_test() -> (Self, T)
-    where
-        Self: Sized;
+    /// Puzzle parameters of this day, `()` if there are none.
+    type Params: Params;
 
-    fn expected_results() -> (Answer, Answer);
+    /// Whether this day has been solved yet, placeholder days return false.
+    fn implemented() -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
 
     /// Parse input
-    fn init(input: &str) -> (Self, T)
+    fn init(input: &str, ctx: &RunContext<Self::Params>) -> Result<(Self, T), ParseError>
     where
         Self: Sized;
 
     /// Compute part 1
-    fn one(&self, data: &mut T) -> Answer;
+    fn one(&self, data: &mut T, ctx: &RunContext<Self::Params>) -> Answer;
 
     /// Compue part 2
-    fn two(&self, data: &mut T) -> Answer;
+    fn two(&self, data: &mut T, ctx: &RunContext<Self::Params>) -> Answer;
 
     /// Parse input and messure the time it took
-    fn init_timed(input: &str) -> ((Self, T), Duration)
+    fn init_timed(
+        input: &str,
+        ctx: &RunContext<Self::Params>,
+    ) -> Result<((Self, T), Duration), ParseError>
     where
         Self: Sized,
     {
         let s = Instant::now();
-        (Self::init(input), s.elapsed())
+        let parsed = log::scoped(Some(ctx.scope()), || Self::init(input, ctx))?;
+        Ok((parsed, s.elapsed()))
     }
 
     /// Compute part 1 and messure the time it took
-    fn one_timed(&self, data: &mut T) -> (Answer, Duration) {
+    fn one_timed(&self, data: &mut T, ctx: &RunContext<Self::Params>) -> (Answer, Duration) {
         let s = Instant::now();
-        (self.one(data), s.elapsed())
+        let answer = log::scoped(Some(ctx.scope()), || self.one(data, ctx));
+        (answer, s.elapsed())
     }
 
     /// Compute part 2 and messure the time it took
-    fn two_timed(&self, data: &mut T) -> (Answer, Duration) {
+    fn two_timed(&self, data: &mut T, ctx: &RunContext<Self::Params>) -> (Answer, Duration) {
         let s = Instant::now();
-        (self.two(data), s.elapsed())
+        let answer = log::scoped(Some(ctx.scope()), || self.two(data, ctx));
+        (answer, s.elapsed())
     }
 
-    /// Compute both parts
-    fn run(input: &str) -> (Answer, Answer)
+    /// Parse the input and compute the requested parts, each one is given up on once it
+    /// took longer than `timeout`. With `parallel`, both parts are computed at the same time.
+    /// Panics are caught, a panic while parsing is returned as the error.
+    /// Returns the time and allocations of parsing, and the results of the parts.
+    #[allow(clippy::type_complexity)]
+    fn run_parts(
+        input: &str,
+        ctx: &RunContext<Self::Params>,
+        part: &Part,
+        timeout: Option<Duration>,
+        parallel: bool,
+    ) -> Result<(Duration, Option<AllocStats>, [Option<PartResult>; 2]), Error>
     where
-        Self: Sized,
+        Self: Sized + Send + Sync + 'static,
+        T: Send + 'static,
     {
-        let (day, mut data) = Self::init(input);
-        (day.one(&mut data.clone()), day.two(&mut data))
+        let (parsed, init_memory) = memory::measure(|| isolated(|| Self::init_timed(input, ctx)));
+        let ((day, data), init_t) = parsed??;
+        let day = Arc::new(day);
+        let run = |n: u8, mut data: T| {
+            let day = day.clone();
+            let ctx = ctx.for_part(n);
+            limited(timeout, ctx.cancel_token().clone(), move || match n {
+                1 => day.one_timed(&mut data, &ctx),
+                _ => day.two_timed(&mut data, &ctx),
+            })
+        };
+
+        let results = match part {
+            Part::Both if parallel => std::thread::scope(|scope| {
+                let two_data = data.clone();
+                let two = scope.spawn(|| run(2, two_data));
+                let one = run(1, data);
+                [Some(one), Some(two.join().unwrap())]
+            }),
+            Part::Both => [Some(run(1, data.clone())), Some(run(2, data))],
+            Part::One => [Some(run(1, data)), None],
+            Part::Two => [None, Some(run(2, data))],
+        };
+        Ok((init_t, init_memory, results))
     }
 
-    /// Init and compute part 1
-    fn run_one(input: &str) -> Answer
+    /// Measure the time of parsing and each requested part once, for benchmarking.
+    /// Every part gets its own copy of the data, copying it isn't measured.
+    fn bench_sample(
+        input: &str,
+        ctx: &RunContext<Self::Params>,
+        part: &Part,
+    ) -> Result<[Duration; 3], ParseError>
     where
         Self: Sized,
     {
-        let (day, mut data) = Self::init(input);
-        day.one(&mut data)
+        let ((day, data), init_t) = Self::init_timed(input, ctx)?;
+        let mut times = [init_t, Duration::ZERO, Duration::ZERO];
+        if *part != Part::Two {
+            times[1] = day.one_timed(&mut data.clone(), &ctx.for_part(1)).1;
+        }
+        if *part != Part::One {
+            times[2] = day.two_timed(&mut data.clone(), &ctx.for_part(2)).1;
+        }
+        Ok(times)
     }
+}
 
-    /// Init and compute part 1
-    fn run_two(input: &str) -> Answer
-    where
-        Self: Sized,
-    {
-        let (day, mut data) = Self::init(input);
-        day.two(&mut data)
-    }
+/// Object safe version of [`DayImpl`], so days can be stored and driven at runtime.
+pub trait DynDay: Send + Sync {
+    /// The number of the day
+    fn day(&self) -> u8;
+
+    fn implemented(&self) -> bool;
+
+    /// Check that the parameters exist and their values are valid.
+    fn check_params(&self, kind: InputKind, params: &[(String, String)]) -> Result<(), ParamError>;
+
+    /// See [`DayImpl::run_parts`], the context is built from the options.
+    #[allow(clippy::type_complexity)]
+    fn run_parts(
+        &self,
+        input: &str,
+        kind: InputKind,
+        params: &[(String, String)],
+        part: &Part,
+        options: &RunOptions,
+        parallel: bool,
+    ) -> Result<(Duration, Option<AllocStats>, [Option<PartResult>; 2]), Error>;
+
+    fn bench_sample(
+        &self,
+        input: &str,
+        part: &Part,
+        kind: InputKind,
+        params: &[(String, String)],
+    ) -> Result<[Duration; 3], Error>;
+}
 
-    /// Init and compute part 1
-    fn run_one_timed(input: &str) -> (Answer, Duration, Duration)
-    where
-        Self: Sized,
-    {
-        let ((day, mut data), init_t) = Self::init_timed(input);
-        let (one, one_t) = day.one_timed(&mut data);
-        (one, init_t, one_t)
-    }
+/// Registry entry wrapping a [`DayImpl`], the data type is only needed to pick the impl.
+pub struct Entry<D, T> {
+    day: u8,
+    marker: PhantomData<fn() -> (D, T)>,
+}
 
-    /// Init and compute part 1
-    fn run_two_timed(input: &str) -> (Answer, Duration, Duration)
-    where
-        Self: Sized,
-    {
-        let ((day, mut data), init_t) = Self::init_timed(input);
-        let (two, two_t) = day.two_timed(&mut data);
-        (two, init_t, two_t)
+impl<D, T> Entry<D, T> {
+    pub fn new(day: u8) -> Self {
+        Self {
+            day,
+            marker: PhantomData,
+        }
     }
+}
 
-    /// Compute both parts, and messure the time each step took
-    fn run_timed(input: &str) -> (Answer, Answer, Duration, Duration, Duration)
-    where
-        Self: Sized,
-    {
-        let ((day, mut data), i_t) = Self::init_timed(input);
-        let (one, one_t) = day.one_timed(&mut data.clone());
-        let (two, two_t) = day.two_timed(&mut data);
+impl<D, T> DynDay for Entry<D, T>
+where
+    D: DayImpl<T> + Send + Sync + 'static,
+    T: Clone + Send + 'static,
+{
+    fn day(&self) -> u8 {
+        self.day
+    }
 
-        (one, two, i_t, one_t, two_t)
+    fn implemented(&self) -> bool {
+        D::implemented()
     }
 
-    /// Test part one
-    fn test_one() -> (bool, Answer, Answer)
-    where
-        Self: Sized,
-    {
-        let (day, mut data) = Self::init_test();
-        let one = day.one(&mut data);
+    fn check_params(&self, kind: InputKind, params: &[(String, String)]) -> Result<(), ParamError> {
+        build_params::<D::Params>(self.day, kind, params).map(|_| ())
+    }
 
-        let (one_e, _) = Self::expected_results();
+    fn run_parts(
+        &self,
+        input: &str,
+        kind: InputKind,
+        params: &[(String, String)],
+        part: &Part,
+        options: &RunOptions,
+        parallel: bool,
+    ) -> Result<(Duration, Option<AllocStats>, [Option<PartResult>; 2]), Error> {
+        let mut ctx = RunContext::new(self.day, kind, build_params(self.day, kind, params)?);
+        if let Some(filter) = &options.log {
+            ctx = ctx.with_level(filter.day_level(self.day));
+        }
+        if let Some(sink) = &options.progress {
+            ctx = ctx.with_progress(sink.clone());
+        }
+        D::run_parts(input, &ctx, part, options.timeout, parallel)
+    }
 
-        (one_e == one, one, one_e)
+    fn bench_sample(
+        &self,
+        input: &str,
+        part: &Part,
+        kind: InputKind,
+        params: &[(String, String)],
+    ) -> Result<[Duration; 3], Error> {
+        let ctx = RunContext::new(self.day, kind, build_params(self.day, kind, params)?);
+        Ok(isolated(|| D::bench_sample(input, &ctx, part))??)
     }
+}
 
-    /// Test part two
-    fn test_two() -> (bool, Answer, Answer)
-    where
-        Self: Sized,
-    {
-        let (day, mut data) = Self::init_test();
-        let two = day.two(&mut data);
+/// All days, ordered by their number.
+pub struct Registry {
+    days: Vec<Box<dyn DynDay>>,
+}
 
-        let (_, two_e) = Self::expected_results();
+impl Registry {
+    pub fn new() -> Self {
+        Self {
+            days: register_days!(),
+        }
+    }
 
-        (two_e == two, two, two_e)
+    /// Look up a day by its number.
+    pub fn get(&self, day: u8) -> Option<&dyn DynDay> {
+        self.days
+            .iter()
+            .find(|d| d.day() == day)
+            .map(|d| d.as_ref())
     }
 
-    /// Run both tests
-    fn test() -> ((bool, Answer, Answer), (bool, Answer, Answer))
-    where
-        Self: Sized,
-    {
-        let (day, mut data) = Self::init_test();
-        let one = day.one(&mut data.clone());
-        let two = day.two(&mut data);
+    pub fn iter(&self) -> impl Iterator<Item = &dyn DynDay> {
+        self.days.iter().map(|d| d.as_ref())
+    }
 
-        let (one_e, two_e) = Self::expected_results();
+    /// The numbers of all registered days.
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.days.iter().map(|d| d.day())
+    }
+}
 
-        ((one_e == one, one, one_e), (two_e == two, two, two_e))
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 mod_days!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_equal_across_variants() {
+        assert_eq!(Answer::Number(42), Answer::Signed(42));
+        assert_eq!(Answer::Signed(42), Answer::Wide(42));
+        assert_eq!(Answer::Wide(42), Answer::WideUnsigned(42));
+        assert_eq!(Answer::Number(u64::MAX), Answer::Wide(u64::MAX as i128));
+        assert_ne!(Answer::Signed(-1), Answer::Number(1));
+        assert_ne!(Answer::Signed(-1), Answer::WideUnsigned(u128::MAX));
+        assert_eq!(Answer::from(u128::MAX), Answer::WideUnsigned(u128::MAX));
+        assert_ne!(Answer::WideUnsigned(u128::MAX), Answer::Wide(i128::MAX));
+    }
+
+    #[test]
+    fn numeric_strings_equal_numbers() {
+        assert_eq!(Answer::String("42".to_owned()), Answer::Number(42));
+        assert_eq!(Answer::Signed(-7), Answer::String("-7".to_owned()));
+        assert_eq!(
+            Answer::String(u128::MAX.to_string()),
+            Answer::WideUnsigned(u128::MAX)
+        );
+        assert_ne!(Answer::String("042".to_owned()), Answer::Number(42));
+        assert_ne!(Answer::String("+42".to_owned()), Answer::Number(42));
+        assert_ne!(Answer::String("42 ".to_owned()), Answer::Number(42));
+    }
+
+    #[test]
+    fn parses_numbers_into_smallest_variant() {
+        assert!(matches!("12".parse(), Ok(Answer::Number(12))));
+        assert!(matches!("-12".parse(), Ok(Answer::Signed(-12))));
+        assert!(matches!(
+            "-9223372036854775809".parse(),
+            Ok(Answer::Wide(-9223372036854775809))
+        ));
+        assert!(matches!(
+            u128::MAX.to_string().parse(),
+            Ok(Answer::WideUnsigned(u128::MAX))
+        ));
+    }
+
+    #[test]
+    fn parses_grids_and_bitmaps() {
+        let grid: Answer = "ab\ncd".parse().unwrap();
+        assert!(matches!(&grid, Answer::Grid(g) if *g == [vec!['a', 'b'], vec!['c', 'd']]));
+
+        let bitmap: Answer = "#.\n.#".parse().unwrap();
+        assert!(
+            matches!(&bitmap, Answer::Bitmap(bm) if *bm == [vec![true, false], vec![false, true]])
+        );
+        assert_eq!(bitmap, Answer::Grid(vec![vec!['#', '.'], vec!['.', '#']]));
+        assert_ne!(bitmap, grid);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::days::Answer;
+
+// Generated by build.rs from the files in `src/days/examples`.
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+/// A single example case of a day.
+///
+/// The input is read from `examples/dNN/<name>.txt`, the expected answers and
+/// parameters from `examples/dNN/<name>.expected`, which looks like this:
+///
+/// ```text
+/// # comment
+/// one = 24000
+/// two =
+/// ##..##..
+/// ###...##
+///
+/// row = 10
+/// ```
+///
+/// An empty value starts a block that ends at the next empty line,
+/// every key except `one` and `two` is a parameter of the day.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub one: Option<Answer>,
+    pub two: Option<Answer>,
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    fn parse(name: &'static str, input: &'static str, expected: &str) -> Result<Self, String> {
+        let mut example = Self {
+            name,
+            input,
+            one: None,
+            two: None,
+            params: Vec::new(),
+        };
+
+        let mut lines = expected.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", i + 1))?;
+            let (key, mut value) = (key.trim(), value.trim().to_owned());
+            if value.is_empty() {
+                value = lines
+                    .by_ref()
+                    .map(|(_, l)| l.trim())
+                    .take_while(|l| !l.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            }
+
+            match key {
+                "one" | "two" => {
+                    let answer = value
+                        .parse()
+                        .map_err(|e| format!("line {}: {}", i + 1, e))?;
+                    match key {
+                        "one" => example.one = Some(answer),
+                        _ => example.two = Some(answer),
+                    }
+                }
+                _ => example.params.push((key.to_owned(), value)),
+            }
+        }
+
+        Ok(example)
+    }
+}
+
+/// All example cases of a day, ordered by name.
+/// Cases with an invalid `.expected` file are returned as an error.
+pub fn examples(day: u8) -> Vec<Result<Example, String>> {
+    EXAMPLE_FILES
+        .iter()
+        .filter(|(d, ..)| *d == day)
+        .map(|(day, name, input, expected)| {
+            Example::parse(name, input, expected)
+                .map_err(|e| format!("examples/d{:02}/{}.expected, {}", day, name, e))
+        })
+        .collect()
+}
@@ -0,0 +1,257 @@
+use crate::tsv::{self, Names};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A step of a day which is benchmarked separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Step {
+    Parsing,
+    One,
+    Two,
+}
+
+/// The names used in the history file.
+const STEP_NAMES: Names<Step> =
+    Names(&[(Step::Parsing, "parse"), (Step::One, "1"), (Step::Two, "2")]);
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Parsing => write!(f, "Parsing"),
+            Self::One => write!(f, "Part 1"),
+            Self::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// The result of benchmarking a single step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub step: Step,
+    pub min: Duration,
+    pub median: Duration,
+}
+
+/// What a benchmark was run with, results are only comparable if these match,
+/// except for the commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Build {
+    /// The commit the binary was built from, with a `-dirty` suffix for uncommitted changes.
+    pub commit: String,
+    pub rustc: String,
+    /// `release` or `debug`.
+    pub profile: String,
+    pub cpu: String,
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+        .map(|(_, model)| model.trim().to_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn cpu_model() -> Option<String> {
+    let output = Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_owned()).filter(|m| !m.is_empty())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn cpu_model() -> Option<String> {
+    None
+}
+
+impl Build {
+    /// The build of the running binary, on the CPU it is running on.
+    pub fn current() -> Self {
+        Self {
+            commit: env!("AOC_GIT_COMMIT").to_owned(),
+            rustc: env!("AOC_RUSTC_VERSION").to_owned(),
+            profile: env!("AOC_PROFILE").to_owned(),
+            cpu: cpu_model().unwrap_or_else(|| std::env::consts::ARCH.to_owned()),
+        }
+    }
+}
+
+/// A benchmark result saved in the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub build: Build,
+    pub measurement: Measurement,
+}
+
+/// The difference between a step and the same step of an earlier benchmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub day: u8,
+    pub step: Step,
+    /// The median of the earlier benchmark.
+    pub before: Duration,
+    pub after: Duration,
+    /// How much slower it got in percent, negative if it got faster.
+    pub change: f64,
+}
+
+/// The results of all benchmarks which were run, stored as one tab separated
+/// `timestamp commit profile rustc cpu day step min_ns median_ns` line per step.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Load the history from `path`, without the file nothing was benchmarked yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Ok(Self {
+            path: path.to_owned(),
+            entries: tsv::load(path, "invalid entry", Self::parse_line)?,
+        })
+    }
+
+    fn parse_line(line: &str) -> Option<Entry> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let build = Build {
+            commit: fields.next()?.to_owned(),
+            profile: fields.next()?.to_owned(),
+            rustc: fields.next()?.to_owned(),
+            cpu: fields.next()?.to_owned(),
+        };
+        let measurement = Measurement {
+            day: fields.next()?.parse().ok()?,
+            step: STEP_NAMES.parse(fields.next()?)?,
+            min: Duration::from_nanos(fields.next()?.parse().ok()?),
+            median: Duration::from_nanos(fields.next()?.parse().ok()?),
+        };
+        Some(Entry {
+            timestamp,
+            build,
+            measurement,
+        })
+    }
+
+    /// Save the results of a benchmark.
+    pub fn record(&mut self, build: &Build, measurements: &[Measurement]) -> Result<(), String> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_secs());
+        let entries: Vec<Entry> = measurements
+            .iter()
+            .map(|measurement| Entry {
+                timestamp,
+                build: build.clone(),
+                measurement: measurement.clone(),
+            })
+            .collect();
+
+        let records: Vec<String> = entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    e.timestamp,
+                    e.build.commit,
+                    e.build.profile,
+                    e.build.rustc,
+                    e.build.cpu,
+                    e.measurement.day,
+                    STEP_NAMES.name(&e.measurement.step).unwrap_or_default(),
+                    e.measurement.min.as_nanos(),
+                    e.measurement.median.as_nanos()
+                )
+            })
+            .collect();
+        tsv::append(&self.path, &records)?;
+
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// Compare the results of a benchmark with the latest results of `commit`,
+    /// which were measured with the same profile and compiler on the same CPU.
+    /// Steps which weren't benchmarked at `commit` are left out.
+    pub fn compare(
+        &self,
+        commit: &str,
+        build: &Build,
+        measurements: &[Measurement],
+    ) -> Result<Vec<Delta>, String> {
+        let comparable: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.build.commit == commit)
+            .collect();
+        if comparable.is_empty() {
+            return Err(format!(
+                "{} hasn't been benchmarked, check it out and run `bench` first",
+                commit
+            ));
+        }
+        let comparable: Vec<&Entry> = comparable
+            .into_iter()
+            .filter(|e| e.build.profile == build.profile && e.build.cpu == build.cpu)
+            .collect();
+        if comparable.is_empty() {
+            return Err(format!(
+                "{} has only been benchmarked with another build profile or CPU",
+                commit
+            ));
+        }
+        let comparable: Vec<&Entry> = comparable
+            .into_iter()
+            .filter(|e| e.build.rustc == build.rustc)
+            .collect();
+        if comparable.is_empty() {
+            return Err(format!(
+                "{} has only been benchmarked with another compiler, not {}",
+                commit, build.rustc
+            ));
+        }
+
+        Ok(measurements
+            .iter()
+            .filter_map(|m| {
+                let before = comparable
+                    .iter()
+                    .filter(|e| e.measurement.day == m.day && e.measurement.step == m.step)
+                    .max_by_key(|e| e.timestamp)?
+                    .measurement
+                    .median;
+                // Very fast steps can take 0ns, which would make the change infinite.
+                let nanos = |d: Duration| d.as_nanos().max(1) as f64;
+                let change = (nanos(m.median) / nanos(before) - 1.0) * 100.0;
+                Some(Delta {
+                    day: m.day,
+                    step: m.step,
+                    before,
+                    after: m.median,
+                    change,
+                })
+            })
+            .collect())
+    }
+}
+
+/// The commit a git ref like `main` or `HEAD~2` points to.
+/// Falls back to the ref itself, so commits with uncommitted changes can be given like `<commit>-dirty`.
+pub fn resolve_commit(reference: &str) -> String {
+    Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{}^{{commit}}", reference))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .unwrap_or_else(|| reference.to_owned())
+}
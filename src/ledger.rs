@@ -0,0 +1,48 @@
+use crate::tsv;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The known correct answers for the user's own input, so runs on the real
+/// input can be checked just like the examples.
+///
+/// Stored as one tab separated `day part answer` line per answer.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Ledger {
+    /// Load the ledger from `path`, without the file no answers are known yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let answers = tsv::load(path, "expected `day<TAB>part<TAB>answer`", |line| {
+            let mut fields = line.splitn(3, '\t');
+            let day = fields.next()?.trim().parse().ok()?;
+            let part = fields.next()?.trim().parse().ok()?;
+            let answer = fields.next()?.trim();
+            Some(((day, part), answer.to_owned()))
+        })?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            answers: answers.into_iter().collect(),
+        })
+    }
+
+    /// The correct answer of a part, if it is known.
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|a| a.as_str())
+    }
+
+    /// Remember the correct answer of a part, and save the ledger.
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) -> Result<(), String> {
+        self.answers.insert((day, part), answer.to_owned());
+
+        let records: Vec<String> = self
+            .answers
+            .iter()
+            .map(|((day, part), answer)| format!("{}\t{}\t{}", day, part, answer))
+            .collect();
+        tsv::write(&self.path, &records)
+    }
+}
@@ -1,20 +1,53 @@
-use crate::days::Answer;
-use crate::days::Day;
-use crate::days::DayImpl;
-use aoc_macro::*;
-use colored::*;
+use crate::days::DynDay;
+use crate::days::Registry;
+use crate::examples::Example;
+use crate::pool::Pool;
 use lazy_static::lazy_static;
-use mut_static::MutStatic;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+#[macro_use]
+mod log;
+mod bench;
+mod cache;
+mod config;
+mod context;
 mod days;
+mod examples;
+mod history;
+mod ledger;
+mod memory;
+mod ocr;
+mod pool;
+mod puzzle;
+mod render;
+mod report;
+mod scaffold;
+mod submit;
+mod tsv;
+mod unlock;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Verbosity {
-    None,
-    Verbose,
-    Developement,
-}
+pub use crate::bench::{bench_day, print_comparison, BenchOptions};
+pub use crate::cache::{check_input, session_hash, Cache, CacheEntry};
+pub use crate::config::Config;
+pub use crate::context::{CancelToken, Progress, ProgressSink, RunContext};
+
+pub use crate::days::{
+    number, param, token, Answer, Day, DayImpl, Error, InputError, InputKind, Panic, ParamError,
+    Params, ParseError, ParseResult, PartResult,
+};
+pub use crate::history::{resolve_commit, Build, Delta, Entry, History, Measurement, Step};
+pub use crate::ledger::Ledger;
+pub use crate::log::{init_logging, Level, LogFilter, LogOptions, Scope};
+pub use crate::memory::AllocStats;
+pub use crate::puzzle::{parse_puzzle, Puzzle};
+pub use crate::render::{print_run, print_summary, print_test};
+pub use crate::report::{Format, PartReport, RunReport, Status};
+pub use crate::scaffold::new_day;
+pub use crate::submit::{parse_response, Attempt, Attempts, Outcome};
+pub use crate::unlock::{
+    fetch_with_retry, unlock_time, wait_for_unlock, Backoff, Clock, FetchError, OffsetClock,
+    SystemClock,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Part {
@@ -23,52 +56,30 @@ pub enum Part {
     Both,
 }
 
-#[derive(Debug, Clone)]
-pub struct Settings {
-    verbosity: Verbosity,
-}
-
-impl Settings {
-    fn set_verbosity(&mut self, new: Verbosity) {
-        self.verbosity = new;
-    }
-}
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAlloc = memory::CountingAlloc;
 
 lazy_static! {
-    static ref VERBOSITY: MutStatic<Settings> = MutStatic::from(Settings {
-        verbosity: Verbosity::None
-    });
+    static ref REGISTRY: Registry = Registry::new();
 }
 
-pub fn set_verbosity(new_verbosity: Verbosity) {
-    let mut handle = VERBOSITY.write().unwrap();
-    handle.set_verbosity(new_verbosity);
+/// The numbers of all days, including placeholders which have no solution yet.
+pub fn days() -> Vec<u8> {
+    REGISTRY.days().collect()
 }
 
-pub fn get_verbosity() -> Verbosity {
-    VERBOSITY.read().unwrap().verbosity.clone()
+/// A day between 1 and 25, others are reported as not implemented.
+fn get_day(day: u8) -> Result<&'static dyn DynDay, Error> {
+    REGISTRY.get(day).ok_or(Error::NotImplemented(day))
 }
 
-#[macro_export]
-macro_rules! vprintln {
-    ($($arg:tt)*) => {
-        if $crate::get_verbosity() ==  $crate::Verbosity::Verbose || $crate::get_verbosity() ==  $crate::Verbosity::Developement {
-            println!(
-                $($arg)*
-            )
-        }
-    };
-}
-
-#[macro_export]
-macro_rules! dprintln {
-    ($($arg:tt)*) => {
-        if $crate::get_verbosity() == $crate::Verbosity::Developement  {
-            println!(
-                $($arg)*
-            )
-        }
-    };
+/// A day which has a solution, placeholders are reported as not implemented too.
+fn implemented_day(day: u8) -> Result<&'static dyn DynDay, Error> {
+    get_day(day).and_then(|d| match d.implemented() {
+        true => Ok(d),
+        false => Err(Error::NotImplemented(day)),
+    })
 }
 
 fn dynamic_range_time_format(d: &Duration) -> String {
@@ -98,126 +109,597 @@ fn dynamic_range_time_format(d: &Duration) -> String {
     }
 }
 
-pub fn run_day(day: u8, part: Part, input: &str) {
-    println!("{} Day {}", "Starting".green().bold(), day);
-    println!("{}", "-----------------------".green().bold());
-    let (one, two, init_t, one_t, two_t) = match part {
-        Part::Both => match_and_run_day_both!(),
-        Part::One => {
-            let (one, init_t, one_t) = match_and_run_day_one!();
-            (one, Answer::Number(0), init_t, one_t, Duration::ZERO)
+/// Parses durations like `1s`, `250ms`, `1.5s` or `100us`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a valid duration", s))?;
+    let factor = match unit.trim() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" | "" => 1.0,
+        "m" | "min" => 60.0,
+        u => return Err(format!("unknown time unit `{}`", u)),
+    };
+    Ok(Duration::from_secs_f64(value * factor))
+}
+
+/// A `--param key=value` given on the command line.
+/// Written as `DAY.key=value` it only applies to that day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamArg {
+    pub day: Option<u8>,
+    pub key: String,
+    pub value: String,
+}
+
+impl std::str::FromStr for ParamArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key=value`, found `{}`", s))?;
+        let (day, key) = match key.split_once('.') {
+            Some((day, key)) => (
+                Some(
+                    day.parse()
+                        .map_err(|_| format!("`{}` is not a day number", day))?,
+                ),
+                key,
+            ),
+            None => (None, key),
+        };
+        if key.is_empty() {
+            return Err(format!("missing parameter name in `{}`", s));
         }
-        Part::Two => {
-            let (two, init_t, two_t) = match_and_run_day_two!();
-            (Answer::Number(0), two, init_t, Duration::ZERO, two_t)
+
+        Ok(Self {
+            day,
+            key: key.to_owned(),
+            value: value.to_owned(),
+        })
+    }
+}
+
+/// The days given on the command line: a day like `5`, a range like `1-5`, a list like `3,7,12`
+/// (which may contain ranges), `all` or `implemented`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySpec {
+    Days(Vec<u8>),
+    /// Every day from 1 to 25, commands which run solutions skip days which aren't implemented.
+    All,
+    Implemented,
+}
+
+impl DaySpec {
+    /// The selected days in ascending order.
+    pub fn days(&self) -> Vec<u8> {
+        match self {
+            Self::Days(days) => days.clone(),
+            Self::All => (1..=25).collect(),
+            Self::Implemented => REGISTRY
+                .days()
+                .filter(|d| get_day(*d).is_ok_and(|d| d.implemented()))
+                .collect(),
         }
-    };
+    }
 
-    println!("{}:", "Results".green().bold());
-    println!(
-        "\t{}: {}",
-        "Parsing time".green(),
-        dynamic_range_time_format(&init_t).bold().blue()
-    );
-    if part == Part::Both || part == Part::One {
-        println!("\t{}:", "Part 1".green());
-        println!("\t\tSolution: {}", format!("{}", one).bold().blue());
-        println!(
-            "\t\tTook:     {}",
-            dynamic_range_time_format(&one_t).bold().blue()
-        );
+    /// The day, if exactly one day was given.
+    pub fn single(&self) -> Option<u8> {
+        match self {
+            Self::Days(days) if days.len() == 1 => Some(days[0]),
+            _ => None,
+        }
     }
-    if part == Part::Both || part == Part::Two {
-        println!("\t{}:", "Part 2".green());
-        println!("\t\tSolution: {}", format!("{}", two).bold().blue());
-        println!(
-            "\t\tTook:     {}",
-            dynamic_range_time_format(&two_t).bold().blue()
-        );
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.trim().parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        Ok(_) => Err("The day must be between 1 and 25.".to_string()),
+        Err(_) => Err(format!(
+            "`{}` is not a day, expected a number, a range like `1-5`, a list like `3,7,12`, `all` or `implemented`.",
+            day
+        )),
     }
 }
 
-pub fn test_day(day: u8, part: Part) -> bool {
-    println!("{} Day {}", "Testing".green().bold(), day);
-    println!("{}", "-----------------------".green().bold());
-    match part {
-        Part::Both => {
-            let ((one_p, one_r, one_e), (two_p, two_r, two_e)) = match_and_test_day_both!();
+impl std::str::FromStr for DaySpec {
+    type Err = String;
 
-            println!("{}:", "Results".green().bold());
-            println!(
-                "\t{}: {}",
-                "Part 1".green(),
-                match one_p {
-                    true => {
-                        "PASSED".green().bold()
-                    }
-                    false => {
-                        "FAILED".red().bold()
-                    }
-                }
-            );
-            println!("\t\tResult:   {}", format!("{}", one_r).bold().blue());
-            println!("\t\tExpected: {}", format!("{}", one_e).bold().blue());
-
-            println!(
-                "\t{}: {}",
-                "Part 2".green(),
-                match two_p {
-                    true => {
-                        "PASSED".green().bold()
-                    }
-                    false => {
-                        "FAILED".red().bold()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(Self::All),
+            "implemented" => return Ok(Self::Implemented),
+            _ => {}
+        }
+        let mut days = Vec::new();
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(format!("the range `{}` is empty", item));
                     }
+                    days.extend(start..=end);
                 }
-            );
-            println!("\t\tResult:   {}", format!("{}", two_r).bold().blue());
-            println!("\t\tExpected: {}", format!("{}", two_e).bold().blue());
+                None => days.push(parse_day(item)?),
+            }
+        }
+        days.sort_unstable();
+        days.dedup();
+        Ok(Self::Days(days))
+    }
+}
+
+/// The parameters given for `day`, in the order they were given.
+fn params_for(params: &[ParamArg], day: u8) -> Vec<(String, String)> {
+    params
+        .iter()
+        .filter(|p| p.day.is_none_or(|d| d == day))
+        .map(|p| (p.key.clone(), p.value.clone()))
+        .collect()
+}
+
+/// Check the parameters of a day before fetching its input.
+pub fn check_params(day: u8, params: &[ParamArg]) -> Result<(), Error> {
+    get_day(day)?
+        .check_params(InputKind::Real, &params_for(params, day))
+        .map_err(Error::Param)
+}
 
-            !(!two_p || !one_p)
+/// How days are run.
+#[derive(Clone)]
+pub struct RunOptions {
+    /// Run up to this many days, and the two parts of a day, at the same time.
+    /// 0 uses one job per CPU.
+    pub jobs: usize,
+    /// Give up on a part once it took this long, it is reported as a timeout.
+    pub timeout: Option<Duration>,
+    /// Which diagnostics the days write, `None` uses the filter given to [`init_logging`].
+    pub log: Option<LogFilter>,
+    /// Receives the progress days report while they run.
+    pub progress: Option<ProgressSink>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            timeout: None,
+            log: None,
+            progress: None,
         }
-        Part::One => {
-            let (one_p, one_r, one_e) = match_and_test_day_one!();
-
-            println!("{}:", "Results".green().bold());
-            println!(
-                "\t{}: {}",
-                "Part 1".green(),
-                match one_p {
-                    true => {
-                        "PASSED".green().bold()
-                    }
-                    false => {
-                        "FAILED".red().bold()
-                    }
+    }
+}
+
+/// Compute the answer of a single part, for example to submit it.
+pub fn solve_part(day: u8, part: Part, input: &str, params: &[ParamArg]) -> Result<Answer, Error> {
+    let implementation = implemented_day(day)?;
+    let (_, _, [one, two]) = implementation.run_parts(
+        input.trim_end(),
+        InputKind::Real,
+        &params_for(params, day),
+        &part,
+        &RunOptions::default(),
+        false,
+    )?;
+    let (answer, ..) = match part {
+        Part::Two => two,
+        _ => one,
+    }
+    .unwrap();
+    answer
+}
+
+/// A day and an input to run it on, with the answers it should give.
+struct Case {
+    day: &'static dyn DynDay,
+    name: String,
+    part: Part,
+    input: String,
+    kind: InputKind,
+    params: Vec<(String, String)>,
+    expected: [Option<Answer>; 2],
+}
+
+impl Case {
+    /// Run the day on the real input of the caller.
+    fn real(
+        day: &'static dyn DynDay,
+        part: Part,
+        input: String,
+        params: &[ParamArg],
+        ledger: &Ledger,
+    ) -> Self {
+        Self {
+            day,
+            name: "input".to_owned(),
+            part,
+            input,
+            kind: InputKind::Real,
+            params: params_for(params, day.day()),
+            expected: ledger_answers(ledger, day.day()),
+        }
+    }
+
+    /// Run the day, and compare the answers with the expected ones.
+    /// Both parts are computed at the same time if the pool has a free thread.
+    fn run(&self, pool: &Pool, options: &RunOptions) -> RunReport {
+        let mut report = RunReport::new(self.day.day(), &self.name, self.part.clone());
+        let thread = match self.part {
+            Part::Both => pool.try_thread(),
+            _ => None,
+        };
+        match self.day.run_parts(
+            self.input.trim_end(),
+            self.kind,
+            &self.params,
+            &self.part,
+            options,
+            thread.is_some(),
+        ) {
+            Ok((init_t, init_memory, [one, two])) => {
+                let [one_e, two_e] = self.expected.clone();
+                report.parse_time = Some(init_t);
+                report.parse_memory = init_memory;
+                report.one = one.map(|(answer, time, memory)| PartReport {
+                    answer,
+                    expected: one_e,
+                    time,
+                    memory,
+                });
+                report.two = two.map(|(answer, time, memory)| PartReport {
+                    answer,
+                    expected: two_e,
+                    time,
+                    memory,
+                });
+            }
+            Err(err) => report.error = Some(err),
+        }
+        report.params = self.params.clone();
+        report
+    }
+}
+
+/// Run the cases on the pool, cases which couldn't be prepared are already reports.
+fn run_cases(
+    cases: &[Result<Case, RunReport>],
+    pool: &Pool,
+    options: &RunOptions,
+) -> Vec<RunReport> {
+    pool.map(cases, |case| match case {
+        Ok(case) => case.run(pool, options),
+        Err(report) => report.clone(),
+    })
+}
+
+/// The answers of a day which are known from the ledger.
+fn ledger_answers(ledger: &Ledger, day: u8) -> [Option<Answer>; 2] {
+    [1, 2].map(|part| ledger.get(day, part).and_then(|a| a.parse().ok()))
+}
+
+/// Every example of a day which has an expected answer for one of the requested parts.
+/// Parameters given on the command line override those of the example files.
+fn example_cases(
+    day: &'static dyn DynDay,
+    part: &Part,
+    params: &[ParamArg],
+) -> Vec<Result<Case, RunReport>> {
+    examples::examples(day.day())
+        .into_iter()
+        .filter_map(|example| {
+            let example = match example {
+                Ok(example) => example,
+                Err(err) => {
+                    let mut report = RunReport::new(day.day(), "?", part.clone());
+                    report.error = Some(Error::Input(err));
+                    return Some(Err(report));
                 }
-            );
-            println!("\t\tResult:   {}", format!("{}", one_r).bold().blue());
-            println!("\t\tExpected: {}", format!("{}", one_e).bold().blue());
+            };
+            let case_part = example_part(part, &example)?;
+            Some(Ok(Case {
+                day,
+                name: example.name.to_owned(),
+                part: case_part,
+                input: example.input.to_owned(),
+                kind: InputKind::Example,
+                params: [example.params, params_for(params, day.day())].concat(),
+                expected: [example.one, example.two],
+            }))
+        })
+        .collect()
+}
 
-            !one_p
+/// Run a day on the real input, and check the answers against the ledger.
+/// With more than one job, both parts are computed at the same time.
+/// If the day couldn't be run or isn't implemented, the report contains the error.
+pub fn run_day(
+    day: u8,
+    part: Part,
+    input: &str,
+    params: &[ParamArg],
+    ledger: &Ledger,
+    options: &RunOptions,
+) -> RunReport {
+    match implemented_day(day) {
+        Ok(day) => Case::real(day, part, input.to_owned(), params, ledger)
+            .run(&Pool::new(options.jobs), options),
+        Err(err) => {
+            let mut report = RunReport::new(day, "input", part);
+            report.error = Some(err);
+            report
         }
-        Part::Two => {
-            let (two_p, two_r, two_e) = match_and_test_day_two!();
-
-            println!("{}:", "Results".green().bold());
-            println!(
-                "\t{}: {}",
-                "Part 2".green(),
-                match two_p {
-                    true => {
-                        "PASSED".green().bold()
-                    }
-                    false => {
-                        "FAILED".red().bold()
-                    }
+    }
+}
+
+/// Solve the requested parts of a day, with the default parameters for the real input.
+///
+/// Timeouts and panics of a single part are reported in its [`PartReport`],
+/// days which can't be run at all are an error.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<RunReport, Error> {
+    implemented_day(day)?;
+    run_day(
+        day,
+        part,
+        input,
+        &[],
+        &Ledger::default(),
+        &RunOptions::default(),
+    )
+    .into_result()
+}
+
+/// The parts of `part` which `example` has an expected answer for.
+fn example_part(part: &Part, example: &Example) -> Option<Part> {
+    let one = *part != Part::Two && example.one.is_some();
+    let two = *part != Part::One && example.two.is_some();
+    match (one, two) {
+        (true, true) => Some(Part::Both),
+        (true, false) => Some(Part::One),
+        (false, true) => Some(Part::Two),
+        (false, false) => None,
+    }
+}
+
+/// The results of every example of a day.
+#[derive(Debug, Clone)]
+pub struct TestReport {
+    pub day: u8,
+    /// One report per example, empty if the day has no examples for the requested parts.
+    pub cases: Vec<RunReport>,
+}
+
+impl TestReport {
+    /// Whether every example could be run and gave the expected answers.
+    pub fn passed(&self) -> bool {
+        self.cases
+            .iter()
+            .all(|r| matches!(r.status(), Status::Passed | Status::Done))
+    }
+}
+
+/// Run every example case of a day.
+/// Parameters given on the command line override those of the example files.
+/// If the day doesn't exist or isn't implemented, the only case contains the error.
+pub fn test_day(day: u8, part: Part, params: &[ParamArg], options: &RunOptions) -> TestReport {
+    let cases = match implemented_day(day) {
+        Ok(d) => run_cases(
+            &example_cases(d, &part, params),
+            &Pool::new(options.jobs),
+            options,
+        ),
+        Err(err) => {
+            let mut report = RunReport::new(day, "example", part);
+            report.error = Some(err);
+            vec![report]
+        }
+    };
+    TestReport { day, cases }
+}
+
+/// Where [`run_days`] gets the input of each day from.
+pub enum Source<'a> {
+    /// The example input of every day, answers are checked against the expected results.
+    Examples,
+    /// Input provided by the caller, for example read from a file or downloaded.
+    /// Answers are checked against the ledger.
+    Inputs(&'a dyn Fn(u8) -> Result<String, String>, &'a Ledger),
+}
+
+/// The results of running multiple days at once.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    /// The selected days, including those which aren't implemented and weren't run.
+    pub days: Vec<u8>,
+    /// Whether the examples were run instead of the real inputs.
+    pub examples: bool,
+    pub reports: Vec<RunReport>,
+    /// The time running all days took, which is less than the summed time with multiple jobs.
+    pub wall_time: Duration,
+    pub jobs: usize,
+}
+
+impl Summary {
+    /// Summed time of all days.
+    pub fn total_time(&self) -> Duration {
+        self.reports.iter().map(|r| r.total_time()).sum()
+    }
+
+    pub fn within_budget(&self, budget: Duration) -> bool {
+        self.total_time() <= budget
+    }
+
+    /// Whether every day succeeded and the summed time didn't exceed the budget.
+    /// Answers which don't match the ledger only count as failures if `strict` is set,
+    /// wrong answers for the examples always do.
+    pub fn passed(&self, budget: Option<Duration>, strict: bool) -> bool {
+        let strict = strict || self.examples;
+        budget.is_none_or(|b| self.within_budget(b))
+            && self.reports.iter().all(|r| match r.status() {
+                Status::Passed | Status::Done => true,
+                Status::Failed => !strict,
+                Status::Timeout | Status::Error => false,
+            })
+    }
+}
+
+/// Run or test the implemented days of `days`.
+/// The inputs are read first, then the days, and the parts of each day, are run at the same time.
+pub fn run_days(
+    days: &[u8],
+    part: Part,
+    source: Source,
+    params: &[ParamArg],
+    options: &RunOptions,
+) -> Summary {
+    let cases: Vec<Result<Case, RunReport>> = REGISTRY
+        .iter()
+        .filter(|d| days.contains(&d.day()) && d.implemented())
+        .flat_map(|d| match source {
+            Source::Examples => example_cases(d, &part, params),
+            Source::Inputs(get_input, ledger) => vec![match get_input(d.day()) {
+                Ok(input) => Ok(Case::real(d, part.clone(), input, params, ledger)),
+                Err(err) => {
+                    let mut report = RunReport::new(d.day(), "input", part.clone());
+                    report.error = Some(Error::Input(err));
+                    Err(report)
                 }
+            }],
+        })
+        .collect();
+
+    let pool = Pool::new(options.jobs);
+    let start = Instant::now();
+    let reports = run_cases(&cases, &pool, options);
+    Summary {
+        days: days.to_vec(),
+        examples: matches!(source, Source::Examples),
+        reports,
+        wall_time: start.elapsed(),
+        jobs: pool.jobs(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days_of(spec: &str) -> Result<Vec<u8>, String> {
+        spec.parse::<DaySpec>().map(|spec| spec.days())
+    }
+
+    #[test]
+    fn parses_day_specs() {
+        assert_eq!(days_of("5"), Ok(vec![5]));
+        assert_eq!(days_of("1-3"), Ok(vec![1, 2, 3]));
+        assert_eq!(days_of("7,3,12"), Ok(vec![3, 7, 12]));
+        assert_eq!(days_of("20-22,2, 21 ,4-4"), Ok(vec![2, 4, 20, 21, 22]));
+        assert_eq!(days_of("all"), Ok((1..=25).collect()));
+        assert_eq!("all".parse(), Ok(DaySpec::All));
+        assert_eq!("implemented".parse(), Ok(DaySpec::Implemented));
+        assert_eq!("5".parse::<DaySpec>().unwrap().single(), Some(5));
+        assert_eq!("5-6".parse::<DaySpec>().unwrap().single(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_day_specs() {
+        assert_eq!(days_of("5-3"), Err("the range `5-3` is empty".to_owned()));
+        assert_eq!(
+            days_of("0"),
+            Err("The day must be between 1 and 25.".to_owned())
+        );
+        assert_eq!(
+            days_of("20-26"),
+            Err("The day must be between 1 and 25.".to_owned())
+        );
+        assert!(days_of("").is_err());
+        assert!(days_of("1,,2").is_err());
+        assert!(days_of("one").is_err());
+        assert!(days_of("1-").is_err());
+    }
+
+    /// A day which only has the placeholder, if any is left.
+    fn placeholder() -> Option<u8> {
+        days()
+            .into_iter()
+            .find(|d| get_day(*d).is_ok_and(|d| !d.implemented()))
+    }
+
+    #[test]
+    fn placeholders_arent_solved() {
+        let Some(day) = placeholder() else {
+            return;
+        };
+        assert!(matches!(
+            solve_part(day, Part::One, "1\n2\n3", &[]),
+            Err(Error::NotImplemented(d)) if d == day
+        ));
+        assert!(matches!(
+            solve(day, Part::Both, "1\n2\n3"),
+            Err(Error::NotImplemented(d)) if d == day
+        ));
+
+        let report = run_day(
+            day,
+            Part::Both,
+            "1\n2\n3",
+            &[],
+            &Ledger::default(),
+            &RunOptions::default(),
+        );
+        assert_eq!(report.status(), Status::Error);
+        assert!(matches!(report.error, Some(Error::NotImplemented(d)) if d == day));
+
+        let report = test_day(day, Part::Both, &[], &RunOptions::default());
+        assert!(!report.passed());
+        assert!(matches!(
+            report.cases.as_slice(),
+            [RunReport { error: Some(Error::NotImplemented(d)), .. }] if *d == day
+        ));
+    }
+
+    #[test]
+    fn days_out_of_range_arent_implemented() {
+        for day in [0, 26] {
+            assert!(matches!(
+                check_params(day, &[]),
+                Err(Error::NotImplemented(d)) if d == day
+            ));
+            assert!(matches!(
+                solve_part(day, Part::One, "", &[]),
+                Err(Error::NotImplemented(d)) if d == day
+            ));
+            assert!(matches!(
+                solve(day, Part::Both, ""),
+                Err(Error::NotImplemented(d)) if d == day
+            ));
+
+            let report = run_day(
+                day,
+                Part::Both,
+                "",
+                &[],
+                &Ledger::default(),
+                &RunOptions::default(),
             );
-            println!("\t\tResult:   {}", format!("{}", two_r).bold().blue());
-            println!("\t\tExpected: {}", format!("{}", two_e).bold().blue());
+            assert_eq!(report.status(), Status::Error);
+            assert!(matches!(report.error, Some(Error::NotImplemented(d)) if d == day));
 
-            !two_p
+            let report = test_day(day, Part::Both, &[], &RunOptions::default());
+            assert!(!report.passed());
+            assert!(matches!(
+                report.cases.as_slice(),
+                [RunReport { error: Some(Error::NotImplemented(d)), .. }] if *d == day
+            ));
         }
     }
 }
@@ -0,0 +1,261 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Arguments;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// How important a diagnostic message is, from most to least important.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+impl std::str::FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warn" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            _ => Err(format!(
+                "unknown log level `{}`, expected error, warn, info, debug or trace",
+                s
+            )),
+        }
+    }
+}
+
+/// Which messages are written: those at least as important as the level of their day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    /// The level of messages outside of days, and of days without their own level.
+    pub default: Level,
+    pub days: BTreeMap<u8, Level>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            default: Level::Warn,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    /// Apply a list of directives like `debug` or `d12=trace,d5=info`, later ones win.
+    pub fn parse(&mut self, directives: &str) -> Result<(), String> {
+        for directive in directives.split(',').filter(|d| !d.trim().is_empty()) {
+            match directive.split_once('=') {
+                Some((day, level)) => {
+                    let day = day.trim();
+                    let day = day
+                        .strip_prefix('d')
+                        .unwrap_or(day)
+                        .parse()
+                        .map_err(|_| format!("`{}` is not a day like `d12`", day))?;
+                    self.days.insert(day, level.parse()?);
+                }
+                None => self.default = directive.parse()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// The level of the messages of a day.
+    pub fn day_level(&self, day: u8) -> Level {
+        self.days.get(&day).copied().unwrap_or(self.default)
+    }
+}
+
+/// Where and how diagnostics are written.
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    pub filter: LogFilter,
+    /// Prefix every message with the time since the program started.
+    pub timestamps: bool,
+    /// Append the messages to this file instead of writing them to stderr.
+    pub file: Option<PathBuf>,
+}
+
+/// The day, and the part of it, which is running on the current thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scope {
+    pub day: u8,
+    /// `None` while parsing.
+    pub part: Option<u8>,
+    /// The least important messages which are written while this day runs.
+    pub level: Level,
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "d{:02}/{}", self.day, part),
+            None => write!(f, "d{:02}/parse", self.day),
+        }
+    }
+}
+
+thread_local! {
+    static SCOPE: Cell<Option<Scope>> = const { Cell::new(None) };
+}
+
+/// The day and part running on the current thread, if any.
+fn current_scope() -> Option<Scope> {
+    SCOPE.with(|s| s.get())
+}
+
+/// Run `f` with messages attributed to `scope` and filtered by its level,
+/// the previous scope is restored afterwards.
+pub fn scoped<R>(scope: Option<Scope>, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Scope>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPE.with(|s| s.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPE.with(|s| s.replace(scope)));
+    f()
+}
+
+struct Logger {
+    options: LogOptions,
+    start: Instant,
+    output: Mutex<Box<dyn Write + Send>>,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+fn logger() -> &'static Logger {
+    LOGGER.get_or_init(|| Logger {
+        options: LogOptions::default(),
+        start: Instant::now(),
+        output: Mutex::new(Box::new(std::io::stderr())),
+    })
+}
+
+/// Set up diagnostics, this can only be done once and before anything is logged.
+/// Without it, warnings and errors are written to stderr.
+/// The filter applies to messages outside of days, and to runs which don't have their own.
+pub fn init_logging(options: LogOptions) -> Result<(), String> {
+    let output: Box<dyn Write + Send> = match &options.file {
+        Some(path) => Box::new(
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| format!("{}: {}", path.display(), err))?,
+        ),
+        None => Box::new(std::io::stderr()),
+    };
+    LOGGER
+        .set(Logger {
+            options,
+            start: Instant::now(),
+            output: Mutex::new(output),
+        })
+        .map_err(|_| "logging has already been set up".to_owned())
+}
+
+/// The level of a day, unless its run has its own filter.
+pub fn default_level(day: u8) -> Level {
+    logger().options.filter.day_level(day)
+}
+
+/// Whether a message at `level` would be written in the current scope.
+pub(crate) fn log_enabled(level: Level) -> bool {
+    level
+        <= current_scope()
+            .map(|s| s.level)
+            .unwrap_or(logger().options.filter.default)
+}
+
+/// Write a message, use [`log!`] and the macros of each level instead.
+pub(crate) fn write_log(level: Level, message: Arguments) {
+    let logger = logger();
+    let mut line = String::new();
+    if logger.options.timestamps {
+        line += &format!("[{:>12.6}s] ", logger.start.elapsed().as_secs_f64());
+    }
+    line += &format!("{:<5} ", level);
+    if let Some(scope) = current_scope() {
+        line += &format!("{}: ", scope);
+    }
+    line += &format!("{}\n", message);
+
+    // Diagnostics must never make a day fail, so write errors are ignored.
+    if let Ok(mut output) = logger.output.lock() {
+        let _ = output.write_all(line.as_bytes());
+    }
+}
+
+// The macros are only available inside the crate, to the modules declared after `mod log`,
+// so they can't collide with those of the `log` crate in programs using this one.
+
+/// Write a diagnostic message at a [`Level`], if the filter of the current day allows it.
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::log_enabled($level) {
+            $crate::log::write_log($level, format_args!($($arg)*));
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        log!($crate::log::Level::Error, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        log!($crate::log::Level::Warn, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        log!($crate::log::Level::Info, $($arg)*)
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        log!($crate::log::Level::Debug, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        log!($crate::log::Level::Trace, $($arg)*)
+    };
+}
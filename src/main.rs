@@ -1,91 +1,337 @@
-use aoc22::{run_day, set_verbosity, test_day, Part, Verbosity};
-use clap::{App, AppSettings, Arg, SubCommand};
+use aoc22::{
+    bench_day, check_input, check_params, fetch_with_retry, init_logging, new_day, parse_duration,
+    parse_puzzle, parse_response, print_comparison, print_run, print_summary, print_test,
+    resolve_commit, run_day, run_days, session_hash, solve_part, test_day, wait_for_unlock,
+    Attempt, Attempts, Backoff, BenchOptions, Build, Cache, Clock, Config, DaySpec, Error,
+    FetchError, Format, History, Ledger, Level, LogFilter, LogOptions, OffsetClock, Outcome,
+    ParamArg, Part, Progress, ProgressSink, Puzzle, RunOptions, RunReport, Source, Status, Summary,
+    SystemClock,
+};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command, ValueHint};
+use clap_complete::Shell;
 use colored::*;
 use reqwest::blocking::Client;
 use reqwest::cookie::Jar;
 use reqwest::header::USER_AGENT;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
 
-// NOTE: Since this CLI was coded using clap in december of 2021, there seem to
-// have been huge breaking changes, requiring me to rewrite the CLI completely
-// to be able to update Clap. As I am on a limited time schedule RN, I will
-// probably do this later on.
-// TODO: Rewrite CLI and update Clap
+fn session_arg() -> Arg {
+    Arg::new("session")
+        .help("The AoC browser session string. If not provided, uses the AOC_SESSION eviroment variable or the config file.")
+        .short('s')
+        .long("session")
+}
 
-fn main() {
-    let matches = App::new("Advent Of Code 2022")
+fn file_arg(help: &'static str) -> Arg {
+    Arg::new("file").help(help).short('f').long("file")
+}
+
+fn budget_arg() -> Arg {
+    Arg::new("budget")
+        .help("Fail if all days together take longer than this, for example `1s` or `500ms`. Only used with multiple days.")
+        .long("budget")
+        .value_parser(parse_duration)
+}
+
+fn format_arg() -> Arg {
+    Arg::new("format")
+        .help("How to print the results. `json` and `csv` are meant for scripts, see the README for their schema.")
+        .long("format")
+        .default_value("text")
+        .value_parser(PossibleValuesParser::new(["text", "json", "csv"]).map(|v| v.parse::<Format>().unwrap()))
+}
+
+fn jobs_arg() -> Arg {
+    Arg::new("jobs")
+        .help("Run up to this many days, and the two parts of a day, at the same time. 0 uses one job per CPU.")
+        .short('j')
+        .long("jobs")
+        .default_value("1")
+        .value_parser(value_parser!(usize))
+}
+
+fn timeout_arg() -> Arg {
+    Arg::new("timeout")
+        .help("Give up on a part once it took longer than this, for example `10s`. It is reported as TIMEOUT.")
+        .long("timeout")
+        .value_parser(parse_duration)
+}
+
+fn strict_arg() -> Arg {
+    Arg::new("strict")
+        .help("Exit with an error if an answer doesn't match the ledger of known answers.")
+        .long("strict")
+        .action(ArgAction::SetTrue)
+}
+
+fn record_arg() -> Arg {
+    Arg::new("record")
+        .help("Save the computed answers in the ledger of known answers, replacing the ones in there.")
+        .long("record")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("strict")
+}
+
+fn flag(name: &'static str, long: &'static str, help: &'static str) -> Arg {
+    Arg::new(name)
+        .help(help)
+        .long(long)
+        .action(ArgAction::SetTrue)
+}
+
+/// The command line interface, also used to generate completions and the man page.
+fn cli() -> Command {
+    Command::new("aoc22")
         .author("LeMoonStar <webmaster@unitcore.de>")
         .about("My Advent Of Code 2022 solutions.")
-        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand_required(true)
+        .arg_required_else_help(true)
         .arg(
-            Arg::with_name("day")
-                .help("The number of the day to execute")
-                .required(true)
-                .takes_value(true)
-                .validator(|v| match v.parse::<u8>() {
-                    Ok(day) => {
-                        if 0 < day && day <= 25 {
-                            Ok(())
-                        } else {
-                            Err("The day must be between 1 and 25.".to_string())
-                        }
-                    }
-                    Err(_) => Err("The day must be a number between 1 and 25.".to_string()),
-                }),
+            Arg::new("day")
+                .help("The days to use: a day like `5`, a range like `1-5`, a list like `3,7,12`, `all` or `implemented`. Optional for `cache`.")
+                .value_parser(|v: &str| v.parse::<DaySpec>()),
         )
         .arg(
-            Arg::with_name("part")
+            Arg::new("part")
                 .help("Specifies the part of the day to compute.")
                 .long("part")
-                .short("p")
+                .short('p')
                 .default_value("b")
-                .possible_values(&["1", "2", "b"])
-                .takes_value(true),
+                .value_parser(["1", "2", "b"]),
         )
         .arg(
-            Arg::with_name("verbose")
-                .help("Print verbose information")
-                .long("verbose")
-                .short("v")
-                .conflicts_with("developement"))
+            Arg::new("param")
+                .help("Override a puzzle parameter, like the row to check in day 15. Use `DAY.key=value` to only set it for a single day.")
+                .long("param")
+                .value_name("key=value")
+                .action(ArgAction::Append)
+                .value_parser(|v: &str| v.parse::<ParamArg>()),
+        )
+        .arg(
+            Arg::new("config")
+                .help("The config file to use. If not provided, uses the AOC_CONFIG enviroment variable or the default location, see the README.")
+                .long("config")
+                .value_hint(ValueHint::FilePath)
+                .global(true),
+        )
+        .arg(
+            Arg::new("year")
+                .help("The year of the puzzles to download and submit, defaults to 2022.")
+                .long("year")
+                .global(true),
+        )
+        .arg(
+            Arg::new("base_url")
+                .help("The AoC server, for example a local stand-in for testing. Defaults to https://adventofcode.com.")
+                .long("base-url")
+                .value_hint(ValueHint::Url)
+                .global(true),
+        )
+        .arg(
+            Arg::new("cache_dir")
+                .help("Where downloaded inputs, known answers and submitted attempts are stored.")
+                .long("cache-dir")
+                .value_hint(ValueHint::DirPath)
+                .global(true),
+        )
         .arg(
-            Arg::with_name("developement")
-                .help("Print developement information")
-                .long("dev")
-                .short("d")
-                .conflicts_with("verbose"))
+            flag("verbose", "verbose", "Print informational diagnostics, like `--log info`.")
+                .short('v')
+                .conflicts_with("developement"),
+        )
+        .arg(
+            flag("developement", "dev", "Print debug diagnostics, like `--log debug`.")
+                .short('d')
+                .conflicts_with("verbose"),
+        )
+        .arg(
+            Arg::new("log")
+                .help("Which diagnostics to print: a level for everything and/or levels for single days, like `debug` or `info,d12=trace`. The levels are error, warn, info, debug and trace, the default is warn.")
+                .long("log")
+                .value_name("FILTER")
+                .action(ArgAction::Append)
+                .value_parser(|v: &str| LogFilter::default().parse(v).map(|_| v.to_owned())),
+        )
+        .arg(
+            Arg::new("log_file")
+                .help("Append diagnostics to this file instead of printing them to stderr.")
+                .long("log-file")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath),
+        )
+        .arg(flag("log_timestamps", "log-timestamps", "Prefix diagnostics with the time since the start."))
+        .arg(flag("progress", "progress", "Print the progress of parts which report it to stderr, in steps of 10%."))
         .subcommand(
-            SubCommand::with_name("test").about("Test the day with the example input data."),
+            Command::new("test")
+                .about("Test the day with the example input data.")
+                .arg(budget_arg())
+                .arg(jobs_arg())
+                .arg(timeout_arg())
+                .arg(format_arg()),
         )
         .subcommand(
-            SubCommand::with_name("auto")
+            Command::new("auto")
                 .about("Automatically download input from AoC using the provided session and run the solution.")
-                .arg(Arg::with_name("session")
-                    .help("The AoC browser session string. If not provided, uses the AOC_SESSION eviroment variable.")
-                    .short("s")
-                    .long("session")
-                    .takes_value(true))
-                .arg(Arg::with_name("no_cache")
-                    .help("Dont cache the input, and delete any current cache for this day.")
-                    .short("N")
-                    .long("no-cache")))
+                .arg(session_arg())
+                .arg(flag("no_cache", "no-cache", "Dont cache the input, and delete any current cache for this day.").short('N'))
+                .arg(flag("wait", "wait", "If the puzzle isn't unlocked yet, wait for it with a countdown and run the solution as soon as the input is available."))
+                .arg(budget_arg())
+                .arg(jobs_arg())
+                .arg(timeout_arg())
+                .arg(format_arg())
+                .arg(strict_arg())
+                .arg(record_arg()),
+        )
         .subcommand(
-            SubCommand::with_name("run")
+            Command::new("run")
                 .about("Use either a file or stdin as input and run the solution.")
-                .arg(Arg::with_name("file")
-                    .help("Specify a file to be used as input, otherwise use stdin.")
-                    .short("f")
-                    .long("file")
-                    .takes_value(true)
-            )
+                .arg(file_arg("Specify a file to be used as input, otherwise use stdin. With multiple days, `{day}` is replaced by the two digit day number, e.g. `inputs/{day}.txt`.")
+                    .value_hint(ValueHint::FilePath))
+                .arg(budget_arg())
+                .arg(jobs_arg())
+                .arg(timeout_arg())
+                .arg(format_arg())
+                .arg(strict_arg())
+                .arg(record_arg()),
+        )
+        .subcommand(
+            Command::new("bench")
+                .about("Benchmark parsing and both parts separately, using a file or the same input as `auto`.")
+                .arg(file_arg("Specify a file to be used as input, otherwise the input is downloaded like with `auto`. With multiple days, `{day}` is replaced by the two digit day number.")
+                    .value_hint(ValueHint::FilePath))
+                .arg(session_arg())
+                .arg(Arg::new("warmup")
+                    .help("Number of iterations to run before measuring.")
+                    .long("warmup")
+                    .default_value("3")
+                    .value_parser(value_parser!(usize)))
+                .arg(Arg::new("iterations")
+                    .help("Number of iterations to measure, instead of measuring for a fixed time.")
+                    .short('n')
+                    .long("iterations")
+                    .value_parser(value_parser!(usize)))
+                .arg(Arg::new("time")
+                    .help("How long to measure for, for example `3s` or `500ms`. Defaults to 3 seconds.")
+                    .short('t')
+                    .long("time")
+                    .conflicts_with("iterations")
+                    .value_parser(parse_duration))
+                .arg(Arg::new("pin")
+                    .help("Pin the benchmark to a CPU core to reduce variance. Only supported on Linux.")
+                    .long("pin")
+                    .value_name("CPU")
+                    .value_parser(value_parser!(usize)))
+                .arg(Arg::new("compare")
+                    .help("Compare the medians with the latest benchmark of a git commit or ref, like `main` or `HEAD~1`, which was run with the same build profile on the same CPU. Exits with an error if a step got slower by more than the threshold.")
+                    .long("compare")
+                    .value_name("REF"))
+                .arg(Arg::new("threshold")
+                    .help("How many percent slower a step may get before `--compare` reports it as a slowdown.")
+                    .long("threshold")
+                    .value_name("PERCENT")
+                    .default_value("10")
+                    .value_parser(value_parser!(f64)))
+                .arg(flag("no_history", "no-history", "Don't save the results in the benchmark history.")),
+        )
+        .subcommand(
+            Command::new("new")
+                .about("Generate the module of a day from a template, with an example using the first example candidate of `fetch-puzzle`. Days which already contain code are never overwritten.")
+                .arg(Arg::new("new_day")
+                    .help("The days to generate, can also be given before the subcommand.")
+                    .value_name("DAY")
+                    .value_parser(|v: &str| v.parse::<DaySpec>())),
+        )
+        .subcommand(
+            Command::new("fetch-puzzle")
+                .about("Download the puzzle text as markdown and save its code blocks as example candidates. Once part 1 is solved, it is downloaded again for part 2.")
+                .arg(session_arg())
+                .arg(flag("refresh", "refresh", "Download the puzzle again, even if it has been saved before.")),
         )
-        .get_matches();
+        .subcommand(
+            Command::new("cache")
+                .about("Inspect the cache of downloaded inputs. Days restrict the command to those days of the configured year.")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(Command::new("list").about("List all cached inputs."))
+                .subcommand(
+                    Command::new("show")
+                        .about("Print the cached input of a day.")
+                        .arg(session_arg()
+                            .help("The session whose input to show. Without it, the configured session is used, or the only cached input of the day.")),
+                )
+                .subcommand(Command::new("clear").about("Remove cached inputs."))
+                .subcommand(
+                    Command::new("verify")
+                        .about("Check the cached inputs against their checksums and for error pages.")
+                        .arg(flag("fix", "fix", "Remove broken inputs, so they are downloaded again.")),
+                ),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Submit an answer to AoC. Answers which are known to be wrong are never submitted.")
+                .arg(Arg::new("part")
+                    .help("The part to submit, defaults to the first part which hasn't been solved yet.")
+                    .long("part")
+                    .value_parser(["1", "2"]))
+                .arg(Arg::new("answer")
+                    .help("Submit this answer instead of computing it.")
+                    .short('a')
+                    .long("answer"))
+                .arg(file_arg("Compute the answer using this input file, otherwise the input is downloaded like with `auto`.")
+                    .value_hint(ValueHint::FilePath))
+                .arg(session_arg()),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Print a completion script for a shell, e.g. `aoc22 completions bash > /etc/bash_completion.d/aoc22`.")
+                .arg(Arg::new("shell")
+                    .required(true)
+                    .value_parser(value_parser!(Shell))),
+        )
+        .subcommand(Command::new("man").about("Print the man page, e.g. `aoc22 man > aoc22.1`."))
+}
+
+/// The value of an argument which only some subcommands have.
+fn optional<'a, T: Clone + Send + Sync + 'static>(
+    matches: &'a ArgMatches,
+    id: &str,
+) -> Option<&'a T> {
+    matches.try_get_one::<T>(id).ok().flatten()
+}
+
+/// Whether a flag which only some subcommands have is set.
+fn is_set(matches: &ArgMatches, id: &str) -> bool {
+    optional::<bool>(matches, id).copied().unwrap_or(false)
+}
+
+fn main() {
+    let matches = cli().get_matches();
+    let (command, c_matches) = matches.subcommand().unwrap();
+
+    match command {
+        "completions" => {
+            let shell = *c_matches.get_one::<Shell>("shell").unwrap();
+            clap_complete::generate(shell, &mut cli(), "aoc22", &mut std::io::stdout());
+            return;
+        }
+        "man" => {
+            if let Err(err) = clap_mangen::Man::new(cli()).render(&mut std::io::stdout()) {
+                eprintln!("{}: {}", "Error".red().bold(), err);
+                std::process::exit(1);
+            }
+            return;
+        }
+        _ => {}
+    }
 
     if cfg!(debug_assertions) {
-        println!(
+        eprintln!(
             "{}",
             "This binary was built in debug mode. To improve performance, please add --release to the build command."
                 .red()
@@ -93,72 +339,856 @@ fn main() {
         );
     }
 
-    let day = matches
-        .value_of("day")
-        .unwrap()
-        .parse::<u8>()
-        .expect("Failed to parse day argument.");
+    let spec = matches
+        .get_one::<DaySpec>("day")
+        .or(optional::<DaySpec>(c_matches, "new_day"));
+    let days = match spec {
+        Some(spec) => Some(spec.days()),
+        None if command == "cache" => None,
+        None => {
+            eprintln!(
+                "{}: a day, days or `all` are required for `{}`",
+                "Error".red().bold(),
+                command
+            );
+            std::process::exit(1);
+        }
+    };
+    // A single day gets a detailed output, multiple days a summary.
+    let single = spec.and_then(|spec| spec.single());
 
-    let part: Part = match matches.value_of("part") {
+    let part: Part = match matches.get_one::<String>("part").map(|p| p.as_str()) {
         Some("1") => Part::One,
         Some("2") => Part::Two,
         Some("b") => Part::Both,
         _ => panic!("unexpected part argument."),
     };
 
-    if matches.args.contains_key("verbose") {
-        set_verbosity(Verbosity::Verbose);
+    let params: Vec<ParamArg> = matches
+        .get_many::<ParamArg>("param")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    setup_logging(&matches);
+
+    let budget = optional::<Duration>(c_matches, "budget").copied();
+    let format = optional::<Format>(c_matches, "format")
+        .copied()
+        .unwrap_or(Format::Text);
+    let options = RunOptions {
+        jobs: optional::<usize>(c_matches, "jobs").copied().unwrap_or(1),
+        timeout: optional::<Duration>(c_matches, "timeout").copied(),
+        progress: matches
+            .get_flag("progress")
+            .then(|| Arc::new(progress_printer()) as ProgressSink),
+        ..Default::default()
+    };
+    let strict = is_set(c_matches, "strict");
+    let record = is_set(c_matches, "record");
+
+    let config = load_config(&matches);
+    migrate_old_cache(
+        &config,
+        optional::<String>(c_matches, "session").map(|s| s.as_str()),
+    );
+    let mut ledger = load_ledger(&config);
+
+    if command == "cache" {
+        if !cache_command(days.as_deref(), c_matches, &config) {
+            std::process::exit(1);
+        }
+        return;
     }
+    let days = days.unwrap();
 
-    if matches.args.contains_key("developement") {
-        set_verbosity(Verbosity::Developement);
+    let day = match single {
+        Some(day) => day,
+        None => {
+            if record {
+                eprintln!("{}: `--record` needs a single day", "Error".red().bold());
+                std::process::exit(1);
+            }
+            let summarize = |summary: Summary| {
+                print_summary(&summary, format, budget);
+                summary.passed(budget, strict)
+            };
+            let success = match command {
+                "run" => {
+                    let template = require_template(c_matches);
+                    let get_input = |day: u8| read_template(template, day);
+                    summarize(run_days(
+                        &days,
+                        part,
+                        Source::Inputs(&get_input, &ledger),
+                        &params,
+                        &options,
+                    ))
+                }
+                "auto" => {
+                    let (session, cache) = get_session_and_cache(c_matches, &config);
+                    let wait = is_set(c_matches, "wait");
+                    let get_input = |day: u8| get_auto_input(day, &session, cache, wait, &config);
+                    summarize(run_days(
+                        &days,
+                        part,
+                        Source::Inputs(&get_input, &ledger),
+                        &params,
+                        &options,
+                    ))
+                }
+                "test" => summarize(run_days(&days, part, Source::Examples, &params, &options)),
+                "bench" => {
+                    let implemented = DaySpec::Implemented.days();
+                    let days: Vec<u8> = days
+                        .into_iter()
+                        .filter(|d| implemented.contains(d))
+                        .collect();
+                    let template = require_template(c_matches);
+                    let mut history = load_history(&config);
+                    // Every day is benchmarked, even if an earlier one failed or got slower.
+                    let results: Vec<bool> = days
+                        .iter()
+                        .map(|day| match read_template(template, *day) {
+                            Ok(input) => {
+                                bench(*day, part.clone(), &input, &params, c_matches, &mut history)
+                            }
+                            Err(err) => {
+                                print_error(&Error::Input(err));
+                                false
+                            }
+                        })
+                        .collect();
+                    results.into_iter().all(|ok| ok)
+                }
+                // Every day is tried, even if an earlier one failed.
+                "new" => {
+                    let results: Vec<bool> = days.iter().map(|day| new(*day, &config)).collect();
+                    results.into_iter().all(|ok| ok)
+                }
+                "fetch-puzzle" => {
+                    let results: Vec<bool> = days
+                        .iter()
+                        .map(|day| fetch_puzzle_command(*day, c_matches, &config))
+                        .collect();
+                    results.into_iter().all(|ok| ok)
+                }
+                "submit" => {
+                    eprintln!("{}: `submit` needs a single day", "Error".red().bold());
+                    std::process::exit(1);
+                }
+                _ => panic!("Unexpected Subcommand."),
+            };
+            if !success {
+                std::process::exit(1);
+            }
+            return;
+        }
+    };
+
+    if command != "test" {
+        if let Err(err) = check_params(day, &params) {
+            exit_with_error(err);
+        }
     }
 
-    match matches.subcommand() {
-        ("run", c_matches) => {
-            let input = match c_matches {
-                Some(c_matches) => {
-                    if let Some(f) = c_matches.value_of("file") {
-                        fs::read_to_string(Path::new(f)).expect("Error while reading input file")
-                    } else {
-                        get_stdin_day_input(day)
-                    }
+    match command {
+        "run" => {
+            let input = match c_matches.get_one::<String>("file") {
+                Some(f) => {
+                    fs::read_to_string(Path::new(f)).expect("Error while reading input file")
                 }
                 None => get_stdin_day_input(day),
             };
-            run_day(day, part, &input);
+            let report = run_day(day, part, &input, &params, &ledger, &options);
+            print_run(&report, format);
+            match report.into_result() {
+                Ok(report) => check_ledger(&report, &mut ledger, strict, record),
+                Err(err) => exit_with_error(err),
+            }
         }
-        ("auto", c_matches) => {
-            let session: String = match c_matches {
-                Some(c_matches) => match c_matches.value_of("session") {
-                    Some(v) => v.to_owned(),
-                    None => env::var("AOC_SESSION").expect("Neither a session argument nor the AOC_SESSION enviroment variable were provided."),
-                },
-                None => env::var("AOC_SESSION").expect("Neither a session argument nor the AOC_SESSION enviroment variable were provided."),
-            };
-            let cache = if let Some(c_matches) = c_matches {
-                !c_matches.args.contains_key("no_cache")
-            } else {
-                true
+        "auto" => {
+            let (session, cache) = get_session_and_cache(c_matches, &config);
+            let wait = is_set(c_matches, "wait");
+            let input = get_auto_input(day, &session, cache, wait, &config)
+                .unwrap_or_else(|err| exit_with_error(Error::Input(err)));
+            let report = run_day(day, part, &input, &params, &ledger, &options);
+            print_run(&report, format);
+            match report.into_result() {
+                Ok(report) => check_ledger(&report, &mut ledger, strict, record),
+                Err(err) => exit_with_error(err),
+            }
+        }
+        "bench" => {
+            let input = match c_matches.get_one::<String>("file") {
+                Some(f) => {
+                    fs::read_to_string(Path::new(f)).expect("Error while reading input file")
+                }
+                None => {
+                    let (session, cache) = get_session_and_cache(c_matches, &config);
+                    get_auto_input(day, &session, cache, false, &config)
+                        .unwrap_or_else(|err| exit_with_error(Error::Input(err)))
+                }
             };
+            let mut history = load_history(&config);
+            if !bench(day, part, &input, &params, c_matches, &mut history) {
+                std::process::exit(1);
+            }
+        }
+        "submit" => {
+            if !submit(day, &matches, c_matches, &params, &config, &mut ledger) {
+                std::process::exit(1);
+            }
+        }
+        "new" => {
+            if !new(day, &config) {
+                std::process::exit(1);
+            }
+        }
+        "fetch-puzzle" => {
+            if !fetch_puzzle_command(day, c_matches, &config) {
+                std::process::exit(1);
+            }
+        }
+        "test" => {
+            let report = test_day(day, part, &params, &options);
+            print_test(&report, format);
+            if !report.passed() {
+                std::process::exit(1);
+            }
+        }
+        _ => panic!("Unexpected Subcommand."),
+    }
+}
+
+/// The `-f` file of a subcommand which runs multiple days, which has to contain `{day}`.
+fn require_template(c_matches: &ArgMatches) -> &str {
+    c_matches
+        .get_one::<String>("file")
+        .filter(|f| f.contains("{day}"))
+        .unwrap_or_else(|| {
+            eprintln!(
+                "{}: multiple days require a file like `-f inputs/{{day}}.txt`",
+                "Error".red().bold()
+            );
+            std::process::exit(1);
+        })
+}
+
+/// Read the input of a day, `{day}` in the template is replaced by the two digit day number.
+fn read_template(template: &str, day: u8) -> Result<String, String> {
+    let path = template.replace("{day}", &format!("{:02}", day));
+    fs::read_to_string(&path).map_err(|err| format!("{}: {}", path, err))
+}
+
+/// Benchmark a day using the options of `bench` and save the results in the history,
+/// returns whether it succeeded and didn't get slower than the commit it is compared to.
+fn bench(
+    day: u8,
+    part: Part,
+    input: &str,
+    params: &[ParamArg],
+    c_matches: &ArgMatches,
+    history: &mut History,
+) -> bool {
+    let mut options = BenchOptions {
+        warmup: *c_matches.get_one::<usize>("warmup").unwrap(),
+        iterations: c_matches.get_one::<usize>("iterations").copied(),
+        pin: c_matches.get_one::<usize>("pin").copied(),
+        ..Default::default()
+    };
+    if let Some(time) = c_matches.get_one::<Duration>("time") {
+        options.time = *time;
+    }
+    let measurements = match bench_day(day, part, input, params, &options) {
+        Ok(measurements) => measurements,
+        Err(err) => {
+            print_error(&err);
+            return false;
+        }
+    };
+
+    let build = Build::current();
+    // Compared before recording, so comparing with the current commit uses the previous run.
+    let mut ok = true;
+    if let Some(reference) = c_matches.get_one::<String>("compare") {
+        let commit = resolve_commit(reference);
+        let threshold = *c_matches.get_one::<f64>("threshold").unwrap();
+        match history.compare(&commit, &build, &measurements) {
+            Ok(deltas) => ok = print_comparison(reference, &deltas, threshold),
+            Err(err) => {
+                eprintln!("{}: {}", "Couldn't compare".red().bold(), err);
+                ok = false;
+            }
+        }
+    }
+
+    if !c_matches.get_flag("no_history") {
+        if let Err(err) = history.record(&build, &measurements) {
+            eprintln!("Warning! couldnt save the benchmark results: {}", err);
+        }
+    }
+    ok
+}
 
-            let input = get_auto_input(day, &session, cache);
-            run_day(day, part, &input);
+/// Generate a day from the template, returns whether it succeeded.
+fn new(day: u8, config: &Config) -> bool {
+    let example = fs::read_to_string(config.puzzle_dir(day).join("example-1.txt")).ok();
+    match new_day(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        day,
+        example.as_deref(),
+    ) {
+        Ok(created) => {
+            for path in created {
+                println!("{} {}", "Created".green().bold(), path.display());
+            }
+            println!("Fill in the example answers and rebuild, then `test` runs the new day.");
+            true
         }
-        ("test", _) => {
-            if !test_day(day, part) {
+        Err(err) => {
+            eprintln!("{}: {}", "Error".red().bold(), err);
+            false
+        }
+    }
+}
+
+/// Load the config and apply the command line flags to it.
+fn load_config(matches: &ArgMatches) -> Config {
+    let mut config = Config::load(matches.get_one::<String>("config").map(Path::new))
+        .unwrap_or_else(|err| {
+            eprintln!("{}: {}", "Invalid config".red().bold(), err);
+            std::process::exit(1);
+        });
+    for (key, arg) in [
+        ("year", "year"),
+        ("base_url", "base_url"),
+        ("cache_dir", "cache_dir"),
+    ] {
+        if let Some(value) = matches.get_one::<String>(arg) {
+            if let Err(err) = config.set(key, value) {
+                eprintln!("{}: {}", "Invalid argument".red().bold(), err);
                 std::process::exit(1);
             }
         }
+    }
+    config
+}
+
+const PUZZLE_FILE: &str = "puzzle.md";
+
+/// Download the puzzle page and save its text and example candidates.
+fn fetch_puzzle(day: u8, session: &str, config: &Config) -> Result<Puzzle, String> {
+    let client = aoc_client(session, config)?;
+    let response = client
+        .get(config.day_url(day, ""))
+        .header(USER_AGENT, USER_AGENT_VALUE)
+        .send()
+        .map_err(|err| err.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Server error or the puzzle isn't unlocked yet: {}",
+            response.status()
+        ));
+    }
+    let puzzle = parse_puzzle(&response.text().map_err(|err| err.to_string())?);
+    if puzzle.parts.is_empty() {
+        return Err("the page doesn't contain a puzzle".to_owned());
+    }
+
+    let dir = config.puzzle_dir(day);
+    let write = |name: &str, content: &str| {
+        let path = dir.join(name);
+        fs::write(&path, content).map_err(|err| format!("{}: {}", path.display(), err))
+    };
+    fs::create_dir_all(&dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    write(PUZZLE_FILE, &puzzle.markdown())?;
+    for (i, example) in puzzle.examples.iter().enumerate() {
+        write(&format!("example-{}.txt", i + 1), example)?;
+    }
+    Ok(puzzle)
+}
+
+/// Run the `fetch-puzzle` subcommand, returns whether it succeeded.
+fn fetch_puzzle_command(day: u8, c_matches: &ArgMatches, config: &Config) -> bool {
+    let dir = config.puzzle_dir(day);
+    let saved = fs::read_to_string(dir.join(PUZZLE_FILE)).ok();
+    // Only the server knows whether part 1 is solved, the ledger can contain unchecked answers.
+    let solved = Attempts::load(&config.attempts_path())
+        .is_ok_and(|attempts| attempts.correct(day, 1).is_some());
+
+    // Part 2 has to be downloaded once part 1 is solved.
+    let outdated = |saved: &str| solved && !saved.contains("--- Part Two ---");
+    let markdown = match saved {
+        Some(saved) if !c_matches.get_flag("refresh") && !outdated(&saved) => saved,
+        _ => {
+            let (session, _) = get_session_and_cache(c_matches, config);
+            eprintln!("Downloading puzzle for day {}", day);
+            match fetch_puzzle(day, &session, config) {
+                Ok(puzzle) => puzzle.markdown(),
+                Err(err) => {
+                    eprintln!("{}: {}", "Error while downloading puzzle".red().bold(), err);
+                    return false;
+                }
+            }
+        }
+    };
+
+    println!("{}", markdown);
+    let mut candidates: Vec<_> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+        .collect();
+    candidates.sort();
+    if !candidates.is_empty() {
+        println!("{}", "Example candidates".green().bold());
+        for path in candidates {
+            println!("\t{}", path.display());
+        }
+        println!(
+            "Copy the right one to src/days/examples/d{:02}/<name>.txt and add a <name>.expected file next to it.",
+            day
+        );
+    }
+    true
+}
+
+/// Get the session and whether to use the cache from the arguments of `auto`.
+fn get_session_and_cache(c_matches: &ArgMatches, config: &Config) -> (String, bool) {
+    let session = optional::<String>(c_matches, "session")
+        .cloned()
+        .or_else(|| config.session.clone())
+        .unwrap_or_else(|| {
+            eprintln!(
+                "{}: provide it using -s, the AOC_SESSION enviroment variable or the config file.",
+                "No session".red().bold()
+            );
+            std::process::exit(1);
+        });
+    let cache = !is_set(c_matches, "no_cache");
+
+    (session, cache)
+}
+
+const OLD_CACHE_DIR: &str = "./.aoc21_cache";
+
+/// Move the old cache in the current directory to the configured cache directory.
+/// Inputs can only be moved once the session is known, since they are stored by session.
+fn migrate_old_cache(config: &Config, session: Option<&str>) {
+    let old = Path::new(OLD_CACHE_DIR);
+    if !old.is_dir() {
+        return;
+    }
+
+    let _ = fs::create_dir_all(&config.cache_dir);
+    for (name, new) in [
+        ("answers.tsv", config.ledger_path()),
+        ("attempts.tsv", config.attempts_path()),
+    ] {
+        let path = old.join(name);
+        if !path.exists() {
+            continue;
+        }
+        if new.exists() {
+            eprintln!(
+                "Warning! not migrating {}, {} already exists",
+                path.display(),
+                new.display()
+            );
+            continue;
+        }
+        if let Err(err) = fs::copy(&path, &new).and_then(|_| fs::remove_file(&path)) {
+            eprintln!("Warning! couldnt migrate {}: {}", path.display(), err);
+        }
+    }
+
+    match session
+        .map(|s| s.to_owned())
+        .or_else(|| config.session.clone())
+    {
+        Some(session) => {
+            match Cache::new(&config.cache_dir).migrate_inputs(old, config.year, &session) {
+                Ok(moved) if moved > 0 => eprintln!(
+                    "Migrated {} inputs from {} to {}",
+                    moved,
+                    OLD_CACHE_DIR,
+                    config.cache_dir.display()
+                ),
+                Ok(_) => {}
+                Err(err) => eprintln!("Warning! couldnt migrate the cached inputs: {}", err),
+            }
+        }
+        None => eprintln!(
+            "Warning! {} contains inputs which can only be migrated once a session is provided",
+            OLD_CACHE_DIR
+        ),
+    }
+
+    // Only succeeds once everything has been migrated.
+    let _ = fs::remove_dir(old);
+}
+
+/// Run the `cache` subcommands, returns whether they succeeded.
+fn cache_command(days: Option<&[u8]>, c_matches: &ArgMatches, config: &Config) -> bool {
+    let cache = Cache::new(&config.cache_dir);
+    let entries: Vec<_> = cache
+        .entries()
+        .into_iter()
+        .filter(|e| days.is_none_or(|days| days.contains(&e.day) && e.year == config.year))
+        .collect();
+    let current = config.session.as_deref().map(session_hash);
+
+    match c_matches.subcommand() {
+        Some(("list", _)) => {
+            println!(
+                "{} in {}",
+                "Cached inputs".green().bold(),
+                config.cache_dir.display()
+            );
+            for entry in &entries {
+                let size = fs::metadata(&entry.path).map(|m| m.len()).unwrap_or(0);
+                println!(
+                    "\t{} day {:02}  session {}  {:>6} bytes  {}{}",
+                    entry.year,
+                    entry.day,
+                    entry.session_hash,
+                    size,
+                    match entry.read() {
+                        Ok(_) => "ok".green(),
+                        Err(err) => err.red(),
+                    },
+                    match current == Some(entry.session_hash.clone()) {
+                        true => " (current session)",
+                        false => "",
+                    }
+                );
+            }
+            true
+        }
+        Some(("show", show_matches)) => {
+            let Some(&[day]) = days else {
+                eprintln!("{}: `cache show` needs a single day", "Error".red().bold());
+                return false;
+            };
+            let session = show_matches
+                .get_one::<String>("session")
+                .map(|s| session_hash(s))
+                .or(current);
+            let matching: Vec<_> = entries
+                .iter()
+                .filter(|e| session.as_ref().is_none_or(|s| *s == e.session_hash))
+                .collect();
+            match matching.as_slice() {
+                [entry] => match entry.read() {
+                    Ok(input) => {
+                        print!("{}", input);
+                        true
+                    }
+                    Err(err) => {
+                        eprintln!("{}: {}", "Broken cache entry".red().bold(), err);
+                        false
+                    }
+                },
+                [] => {
+                    eprintln!("{}: day {} isn't cached", "Error".red().bold(), day);
+                    false
+                }
+                _ => {
+                    eprintln!(
+                        "{}: day {} is cached for multiple sessions, pick one using -s",
+                        "Error".red().bold(),
+                        day
+                    );
+                    false
+                }
+            }
+        }
+        Some(("clear", _)) => {
+            let mut success = true;
+            for entry in &entries {
+                if let Err(err) = entry.remove() {
+                    eprintln!("{}: {}", "Error".red().bold(), err);
+                    success = false;
+                }
+            }
+            println!(
+                "{} {} cached inputs",
+                "Removed".green().bold(),
+                entries.len()
+            );
+            success
+        }
+        Some(("verify", verify_matches)) => {
+            let fix = verify_matches.get_flag("fix");
+            let mut broken = 0;
+            for entry in &entries {
+                let Err(err) = entry.read() else {
+                    continue;
+                };
+                broken += 1;
+                println!(
+                    "\t{} day {:02}  session {}  {}",
+                    entry.year,
+                    entry.day,
+                    entry.session_hash,
+                    err.red()
+                );
+                if fix {
+                    if let Err(err) = entry.remove() {
+                        eprintln!("{}: {}", "Error".red().bold(), err);
+                    }
+                }
+            }
+            match broken {
+                0 => println!(
+                    "{} {} cached inputs are fine",
+                    "All".green().bold(),
+                    entries.len()
+                ),
+                _ if fix => println!("{} {} broken inputs", "Removed".green().bold(), broken),
+                _ => println!(
+                    "{} of {} cached inputs are broken, use --fix to remove them",
+                    broken.to_string().red().bold(),
+                    entries.len()
+                ),
+            }
+            broken == 0 || fix
+        }
         _ => panic!("Unexpected Subcommand."),
     }
 }
 
+/// Print the progress of every day and part to stderr whenever it passes another 10%.
+fn progress_printer() -> impl Fn(Progress) + Send + Sync {
+    let printed: Mutex<HashMap<(u8, Option<u8>), u64>> = Mutex::default();
+    move |progress| {
+        let percent = progress.done * 100 / progress.total.max(1) / 10 * 10;
+        let mut printed = printed.lock().unwrap();
+        // Another example of the same day starts over.
+        if printed.insert((progress.day, progress.part), percent) == Some(percent) {
+            return;
+        }
+        match progress.part {
+            Some(part) => eprintln!("Day {} part {}: {}%", progress.day, part, percent),
+            None => eprintln!("Day {} parsing: {}%", progress.day, percent),
+        }
+    }
+}
+
+/// Set up diagnostics from `-v`, `-d` and the `--log` options.
+fn setup_logging(matches: &ArgMatches) {
+    let mut options = LogOptions {
+        timestamps: matches.get_flag("log_timestamps"),
+        file: matches.get_one::<String>("log_file").map(PathBuf::from),
+        ..Default::default()
+    };
+    if matches.get_flag("verbose") {
+        options.filter.default = Level::Info;
+    }
+    if matches.get_flag("developement") {
+        options.filter.default = Level::Debug;
+    }
+    for filter in matches.get_many::<String>("log").into_iter().flatten() {
+        // Already checked by clap.
+        let _ = options.filter.parse(filter);
+    }
+    if let Err(err) = init_logging(options) {
+        eprintln!("{}: {}", "Couldn't set up logging".red().bold(), err);
+        std::process::exit(1);
+    }
+}
+
+fn load_history(config: &Config) -> History {
+    History::load(&config.history_path()).unwrap_or_else(|err| {
+        eprintln!("{}: {}", "Invalid benchmark history".red().bold(), err);
+        std::process::exit(1);
+    })
+}
+
+fn load_ledger(config: &Config) -> Ledger {
+    Ledger::load(&config.ledger_path()).unwrap_or_else(|err| {
+        eprintln!("{}: {}", "Invalid ledger".red().bold(), err);
+        std::process::exit(1);
+    })
+}
+
+/// Record the answers in the ledger if requested, and exit if they don't match it in strict mode.
+fn check_ledger(report: &RunReport, ledger: &mut Ledger, strict: bool, record: bool) {
+    if record {
+        for (part, result) in report.parts() {
+            let Ok(answer) = &result.answer else {
+                continue;
+            };
+            let Some(answer) = answer.text() else {
+                eprintln!("Warning! part {} isn't text or its letters couldn't be read, so it isn't saved in the ledger", part);
+                continue;
+            };
+            if let Err(err) = ledger.set(report.day, part, &answer) {
+                eprintln!("Warning! couldnt save the answer in the ledger: {}", err);
+            }
+        }
+    }
+    match report.status() {
+        Status::Timeout | Status::Error => std::process::exit(1),
+        Status::Failed if strict => std::process::exit(1),
+        _ => {}
+    }
+}
+
+/// Submit an answer, returns whether it was correct.
+fn submit(
+    day: u8,
+    matches: &ArgMatches,
+    c_matches: &ArgMatches,
+    params: &[ParamArg],
+    config: &Config,
+    ledger: &mut Ledger,
+) -> bool {
+    let mut attempts = Attempts::load(&config.attempts_path()).unwrap_or_else(|err| {
+        eprintln!("{}: {}", "Invalid attempts file".red().bold(), err);
+        std::process::exit(1);
+    });
+
+    let part: u8 = match c_matches
+        .get_one::<String>("part")
+        .or(matches.get_one::<String>("part"))
+        .map(|p| p.as_str())
+    {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ if attempts.correct(day, 1).is_some() => 2,
+        _ => 1,
+    };
+
+    let answer = match c_matches.get_one::<String>("answer") {
+        Some(answer) => answer.trim().to_owned(),
+        None => {
+            let input = match c_matches.get_one::<String>("file") {
+                Some(f) => {
+                    fs::read_to_string(Path::new(f)).expect("Error while reading input file")
+                }
+                None => {
+                    let (session, cache) = get_session_and_cache(c_matches, config);
+                    get_auto_input(day, &session, cache, false, config)
+                        .unwrap_or_else(|err| exit_with_error(Error::Input(err)))
+                }
+            };
+            let part = match part {
+                1 => Part::One,
+                _ => Part::Two,
+            };
+            let answer = match solve_part(day, part, &input, params) {
+                Ok(answer) => answer,
+                Err(err) => exit_with_error(err),
+            };
+            match answer.text() {
+                Some(text) => text,
+                None => {
+                    eprintln!(
+                        "{}: the answer isn't text or its letters couldn't be read, read it yourself and use --answer:\n{}",
+                        "Error".red().bold(),
+                        answer
+                    );
+                    return false;
+                }
+            }
+        }
+    };
+
+    println!(
+        "{} {} for day {} part {}",
+        "Submitting".green().bold(),
+        answer.bold().blue(),
+        day,
+        part
+    );
+    // Answers in the ledger may have been recorded without being checked, so only those
+    // the server confirmed count.
+    let known = attempts.correct(day, part);
+    if known == Some(answer.as_str()) {
+        println!("{}", "This answer is already known to be correct.".green());
+        return true;
+    }
+    if let Some(known) = known {
+        println!(
+            "{}: the correct answer is already known: {}",
+            "Not submitting".red().bold(),
+            known
+        );
+        return false;
+    }
+    if let Some(reason) = attempts.known_wrong(day, part, &answer) {
+        println!("{}: {}", "Not submitting".red().bold(), reason);
+        return false;
+    }
+
+    let (session, _) = get_session_and_cache(c_matches, config);
+    let outcome = match post_answer(day, part, &answer, &session, config) {
+        Ok(html) => parse_response(&html),
+        Err(err) => {
+            eprintln!("{}: {}", "Error while submitting".red().bold(), err);
+            return false;
+        }
+    };
+
+    match outcome {
+        Outcome::Correct | Outcome::AlreadySolved => {
+            println!("{}", outcome.to_string().green().bold())
+        }
+        _ => println!("{}", outcome.to_string().red().bold()),
+    }
+    let correct = matches!(outcome, Outcome::Correct | Outcome::AlreadySolved);
+    if outcome == Outcome::Correct {
+        if let Err(err) = ledger.set(day, part, &answer) {
+            eprintln!("Warning! couldnt save the answer in the ledger: {}", err);
+        }
+        // The text of part 2 is only shown once part 1 is solved.
+        if part == 1 && config.puzzle_dir(day).join(PUZZLE_FILE).exists() {
+            match fetch_puzzle(day, &session, config) {
+                Ok(_) => println!(
+                    "{} part 2 to {}",
+                    "Fetched".green().bold(),
+                    config.puzzle_dir(day).join(PUZZLE_FILE).display()
+                ),
+                Err(err) => eprintln!("Warning! couldnt fetch part 2 of the puzzle: {}", err),
+            }
+        }
+    }
+    if let Err(err) = attempts.record(Attempt {
+        day,
+        part,
+        answer,
+        outcome,
+    }) {
+        eprintln!("Warning! couldnt save the attempt: {}", err);
+    }
+    correct
+}
+
+fn exit_with_error(err: Error) -> ! {
+    print_error(&err);
+    std::process::exit(1);
+}
+
+fn print_error(err: &Error) {
+    match err {
+        Error::Parse(err) => eprintln!("{}: {}", "Invalid input".red().bold(), err),
+        Error::Param(err) => eprintln!("{}: {}", "Invalid parameter".red().bold(), err),
+        Error::Input(err) => eprintln!("{}: {}", "Missing input".red().bold(), err),
+        Error::Timeout(_) | Error::NotImplemented(_) => {
+            eprintln!("{}", err.to_string().red().bold())
+        }
+        Error::Panic(err) => eprintln!("{}: {}", "Panicked".red().bold(), err),
+    }
+}
+
 fn get_stdin_day_input(day: u8) -> String {
     let mut input = String::new();
     let stdin = std::io::stdin();
 
-    println!(
+    eprintln!(
         "Please paste your input for day {}, and then press {}",
         day,
         match cfg!(windows) {
@@ -181,62 +1211,137 @@ fn get_stdin_day_input(day: u8) -> String {
     input
 }
 
-fn download_input(day: u8, session: &String) -> Result<String, reqwest::Error> {
-    println!("Downloading input for day {}", day);
-
+/// A client which sends the session cookie to the configured server.
+fn aoc_client(session: &str, config: &Config) -> Result<Client, String> {
+    let url = config
+        .base_url
+        .parse::<reqwest::Url>()
+        .map_err(|err| format!("invalid base url `{}`: {}", config.base_url, err))?;
     let cookie_jar = Jar::default();
-    cookie_jar.add_cookie_str(
-        format!("session={}; Domain=adventofcode.com", session).as_ref(),
-        &"https://adventofcode.com/".parse::<reqwest::Url>().unwrap(),
-    );
-    let client = Client::builder()
-        .https_only(true)
+    cookie_jar.add_cookie_str(format!("session={}", session).as_ref(), &url);
+    Client::builder()
+        .https_only(url.scheme() == "https")
         .cookie_provider(Arc::new(cookie_jar))
-        .build()?;
+        .build()
+        .map_err(|err| err.to_string())
+}
+
+const USER_AGENT_VALUE: &str = "https://github.com/LeMoonStar/AoC22 aoc22@unitcore.de";
 
+/// Post an answer and return the html of the response.
+fn post_answer(
+    day: u8,
+    part: u8,
+    answer: &str,
+    session: &str,
+    config: &Config,
+) -> Result<String, String> {
+    let client = aoc_client(session, config)?;
     let response = client
-        .get(format!("https://adventofcode.com/2022/day/{}/input", day))
-        .header(
-            USER_AGENT,
-            "https://github.com/LeMoonStar/AoC22 aoc22@unitcore.de",
-        )
-        .send()?;
+        .post(config.day_url(day, "answer"))
+        .header(USER_AGENT, USER_AGENT_VALUE)
+        .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+        .send()
+        .map_err(|err| err.to_string())?;
 
     if !response.status().is_success() {
-        panic!("Server error or invalid session.");
+        return Err(format!(
+            "Server error or invalid session: {}",
+            response.status()
+        ));
     }
+    response.text().map_err(|err| err.to_string())
+}
+
+fn download_input(day: u8, session: &str, config: &Config) -> Result<String, FetchError> {
+    eprintln!("Downloading input for day {}", day);
 
-    response.text()
+    let client = aoc_client(session, config).map_err(FetchError::Fatal)?;
+    let response = client
+        .get(config.day_url(day, "input"))
+        .header(USER_AGENT, USER_AGENT_VALUE)
+        .send()
+        .map_err(|err| FetchError::Retry(err.to_string()))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status.is_server_error() {
+        return Err(FetchError::Retry(format!(
+            "The input isn't available yet: {}",
+            status
+        )));
+    }
+    if !status.is_success() {
+        return Err(FetchError::Fatal(format!(
+            "Server error or invalid session: {}",
+            status
+        )));
+    }
+    let input = response
+        .text()
+        .map_err(|err| FetchError::Retry(err.to_string()))?;
+    if input.contains("before it unlocks") {
+        return Err(FetchError::Retry(
+            "The puzzle isn't unlocked yet".to_owned(),
+        ));
+    }
+    check_input(&input).map_err(FetchError::Fatal)?;
+    Ok(input)
 }
 
-fn get_auto_input(day: u8, session: &String, cache: bool) -> String {
-    let cache_str = &format!("./.aoc21_cache/input{:02}.txt", day);
-    let cache_path: &Path = Path::new(cache_str);
-    match cache {
-        true => match fs::read_to_string(cache_path) {
-            Ok(input) => input,
-            Err(_) => match download_input(day, session) {
-                Ok(input) => {
-                    let _ = fs::create_dir(Path::new("./.aoc21_cache"));
-                    match fs::write(cache_path, &input) {
-                        Ok(_) => {}
-                        Err(err) => println!("Warning! couldnt save input cache!{:?}", err),
-                    }
-                    input
-                }
-                Err(err) => {
-                    panic!("Error while downloading input: {:?}", err);
-                }
-            },
-        },
-        false => {
-            let _ = fs::remove_file(cache_path);
-            match download_input(day, session) {
-                Ok(input) => input,
-                Err(err) => {
-                    panic!("Error while downloading input: {:?}", err);
-                }
+/// The clock used to wait for puzzles, `AOC_NOW` pretends the current time is the given unix timestamp.
+fn clock() -> Box<dyn Clock> {
+    match env::var("AOC_NOW") {
+        Ok(now) => match now.parse::<u64>() {
+            Ok(secs) => Box::new(OffsetClock::starting_at(
+                UNIX_EPOCH + Duration::from_secs(secs),
+            )),
+            Err(_) => {
+                eprintln!(
+                    "{}: AOC_NOW must be a unix timestamp",
+                    "Invalid enviroment".red().bold()
+                );
+                std::process::exit(1);
             }
+        },
+        Err(_) => Box::new(SystemClock),
+    }
+}
+
+/// Get the input from the cache or download it. With `wait`, wait for the puzzle to unlock
+/// and retry until the input is available.
+fn get_auto_input(
+    day: u8,
+    session: &str,
+    cache: bool,
+    wait: bool,
+    config: &Config,
+) -> Result<String, String> {
+    let input_cache = Cache::new(&config.cache_dir);
+    if cache {
+        if let Some(input) = input_cache.get(config.year, day, session) {
+            return Ok(input);
+        }
+    } else {
+        let _ = input_cache.entry(config.year, day, session).remove();
+    }
+
+    let input = match wait {
+        true => {
+            let clock = clock();
+            wait_for_unlock(clock.as_ref(), config.year, day);
+            fetch_with_retry(clock.as_ref(), &Backoff::default(), || {
+                download_input(day, session, config)
+            })
+        }
+        false => download_input(day, session, config).map_err(|err| match err {
+            FetchError::Retry(err) | FetchError::Fatal(err) => err,
+        }),
+    }
+    .map_err(|err| format!("Error while downloading input: {}", err))?;
+    if cache {
+        if let Err(err) = input_cache.put(config.year, day, session, &input) {
+            eprintln!("Warning! couldnt save input cache: {}", err);
         }
     }
+    Ok(input)
 }
@@ -0,0 +1,139 @@
+use std::cell::Cell;
+
+/// The allocations made during a step, like parsing or a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, growing an allocation counts as one.
+    pub allocations: u64,
+    /// Bytes requested by all allocations together.
+    pub bytes: u64,
+    /// The most bytes which were allocated and not yet freed at any point during the step.
+    pub peak: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    // Counted per thread, so parts running at the same time don't count each other's allocations.
+    // It has no destructor and needs no initialization, so the allocator can always use it.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+#[cfg(feature = "count-allocs")]
+mod allocator {
+    use super::COUNTERS;
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    fn count(allocations: u64, bytes: u64, live: i64) {
+        let _ = COUNTERS.try_with(|c| {
+            let mut counters = c.get();
+            counters.allocations += allocations;
+            counters.bytes += bytes;
+            counters.live += live;
+            counters.peak = counters.peak.max(counters.live);
+            c.set(counters);
+        });
+    }
+
+    /// The system allocator, counting the allocations of each thread.
+    /// It is only used with the `count-allocs` feature, since counting makes allocating slower.
+    pub struct CountingAlloc;
+
+    // SAFETY: every call is passed on to the system allocator, counting never allocates.
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                count(1, layout.size() as u64, layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                count(1, layout.size() as u64, layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            count(0, 0, -(layout.size() as i64));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                count(1, new_size as u64, new_size as i64 - layout.size() as i64);
+            }
+            new
+        }
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+pub use allocator::CountingAlloc;
+
+/// Count the allocations `f` makes on this thread,
+/// `None` unless the counting allocator is used.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !cfg!(feature = "count-allocs") {
+        return (f(), None);
+    }
+
+    let start = COUNTERS.with(|c| {
+        let mut counters = c.get();
+        counters.peak = counters.live;
+        c.set(counters);
+        counters
+    });
+    let result = f();
+    let end = COUNTERS.with(|c| c.get());
+    let stats = AllocStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Format a number of bytes like `1.50 MiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
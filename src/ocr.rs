@@ -0,0 +1,181 @@
+// Recognizes the letters AoC draws in puzzles like 2022 day 10. There are two
+// fonts, the common one is 6 pixels high, the one from 2018 day 10 is 10 pixels high.
+// Letters are separated by at least one empty column.
+
+const FONT_6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const FONT_10: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// A glyph as rows of `#` and `.`, with empty columns at the sides removed.
+fn trimmed(rows: &[String]) -> Vec<String> {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let filled = |x: usize| rows.iter().any(|r| r.as_bytes().get(x) == Some(&b'#'));
+    let start = (0..width).find(|x| filled(*x)).unwrap_or(0);
+    let end = (0..width).rev().find(|x| filled(*x)).map_or(0, |x| x + 1);
+
+    rows.iter()
+        .map(|r| {
+            (start..end)
+                .map(|x| match r.as_bytes().get(x) {
+                    Some(b'#') => '#',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn lookup<const H: usize>(font: &[(char, [&str; H])], glyph: &[String]) -> Option<char> {
+    font.iter()
+        .find(|(_, rows)| trimmed(&rows.map(|r| r.to_owned())) == glyph)
+        .map(|(c, _)| *c)
+}
+
+/// Read the letters drawn in a bitmap, `None` if it contains anything which isn't a known letter.
+pub fn decode(bitmap: &[Vec<bool>]) -> Option<String> {
+    let filled_row = |row: &Vec<bool>| row.iter().any(|p| *p);
+    let top = bitmap.iter().position(filled_row)?;
+    let bottom = bitmap.iter().rposition(filled_row)? + 1;
+    let rows = &bitmap[top..bottom];
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let pixel = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if (0..rows.len()).all(|y| !pixel(x, y)) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && (0..rows.len()).any(|y| pixel(x, y)) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (0..rows.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if pixel(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        text.push(match rows.len() {
+            6 => lookup(FONT_6, &glyph)?,
+            10 => lookup(FONT_10, &glyph)?,
+            _ => return None,
+        });
+    }
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|r| r.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn decodes_screen() {
+        let screen = bitmap(&[
+            "####.#..#.####..##..###....##.#....#..#.",
+            "#....#..#....#.#..#.#..#....#.#....#..#.",
+            "###..####...#..#....#..#....#.#....#..#.",
+            "#....#..#..#...#.##.###.....#.#....#..#.",
+            "#....#..#.#....#..#.#....#..#.#....#..#.",
+            "####.#..#.####..###.#.....##..####..##..",
+        ]);
+        assert_eq!(decode(&screen).as_deref(), Some("EHZGPJLU"));
+    }
+
+    #[test]
+    fn ignores_empty_rows_around_letters() {
+        let screen = bitmap(&[
+            "..........",
+            ".###..###.",
+            ".#..#.#..#",
+            ".#..#.#..#",
+            ".###..###.",
+            ".#....#.#.",
+            ".#....#..#",
+            "..........",
+        ]);
+        assert_eq!(decode(&screen).as_deref(), Some("PR"));
+    }
+
+    #[test]
+    fn decodes_every_glyph() {
+        for (c, rows) in FONT_6 {
+            assert_eq!(decode(&bitmap(rows)), Some(c.to_string()));
+        }
+        for (c, rows) in FONT_10 {
+            assert_eq!(decode(&bitmap(rows)), Some(c.to_string()));
+        }
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let screen = bitmap(&[
+            ".##..###.",
+            "#..#.#..#",
+            "#..#.#..#",
+            "####.#..#",
+            "#..#.#..#",
+            "#..#.###.",
+        ]);
+        assert_eq!(decode(&screen), None);
+    }
+
+    #[test]
+    fn wrong_height() {
+        let screen = bitmap(&[".##.", "#..#", "#..#", "####", "#..#", "#..#", "#..#"]);
+        assert_eq!(decode(&screen), None);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(decode(&bitmap(&["....", "...."])), None);
+        assert_eq!(decode(&[]), None);
+    }
+}
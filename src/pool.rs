@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A fixed number of threads, shared by the days and the parts of each day.
+///
+/// The calling thread always takes part in the work, so a pool of 1 job runs everything
+/// on the calling thread. A day which starts while a thread is free takes it to compute its
+/// second part at the same time as the first one. Days which are already running don't get
+/// threads which become free later.
+#[derive(Debug)]
+pub struct Pool {
+    jobs: usize,
+    free: AtomicUsize,
+}
+
+/// A thread taken from the pool, it is given back when this is dropped.
+pub struct Thread<'a> {
+    pool: &'a Pool,
+}
+
+impl Drop for Thread<'_> {
+    fn drop(&mut self) {
+        self.pool.free.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Pool {
+    /// A pool running up to `jobs` things at once, 0 uses one job per CPU.
+    pub fn new(jobs: usize) -> Self {
+        let jobs = match jobs {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            jobs => jobs,
+        };
+        Self {
+            jobs,
+            free: AtomicUsize::new(jobs - 1),
+        }
+    }
+
+    pub fn jobs(&self) -> usize {
+        self.jobs
+    }
+
+    /// Take an additional thread, if one is free.
+    pub fn try_thread(&self) -> Option<Thread<'_>> {
+        self.free
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |free| {
+                free.checked_sub(1)
+            })
+            .ok()
+            .map(|_| Thread { pool: self })
+    }
+
+    /// Call `job` for every item, the results are in the order of the items.
+    pub fn map<I: Sync, R: Send>(&self, items: &[I], job: impl Fn(&I) -> R + Sync) -> Vec<R> {
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+        let work = || loop {
+            let i = next.fetch_add(1, Ordering::SeqCst);
+            let Some(item) = items.get(i) else {
+                break;
+            };
+            let result = job(item);
+            results.lock().unwrap()[i] = Some(result);
+        };
+
+        std::thread::scope(|scope| {
+            for _ in 1..items.len() {
+                let Some(thread) = self.try_thread() else {
+                    break;
+                };
+                let work = &work;
+                scope.spawn(move || {
+                    work();
+                    drop(thread);
+                });
+            }
+            work();
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+}
@@ -0,0 +1,269 @@
+/// The text of a puzzle, taken from the html of its page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// One markdown document per part, the second one only exists once part 1 is solved.
+    pub parts: Vec<String>,
+    /// The contents of every `<pre><code>` block, which are usually the example inputs.
+    pub examples: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn markdown(&self) -> String {
+        self.parts.join("\n")
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 8) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let c = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|n| u32::from_str_radix(n, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// A html tag, split into its name and the `href` attribute, which is the only one that is used.
+struct Tag<'a> {
+    name: String,
+    closing: bool,
+    href: Option<&'a str>,
+}
+
+impl<'a> Tag<'a> {
+    fn parse(tag: &'a str) -> Self {
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let href = tag
+            .split_once("href=\"")
+            .and_then(|(_, rest)| rest.split_once('"'))
+            .map(|(href, _)| href);
+        Self {
+            name,
+            closing,
+            href,
+        }
+    }
+}
+
+/// Convert the html of a single `<article>` to markdown, collecting the `<pre><code>` blocks.
+fn article_to_markdown(html: &str, examples: &mut Vec<String>) -> String {
+    let mut markdown = String::new();
+    let mut pre: Option<String> = None;
+    let mut links = Vec::new();
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let (text, after) = rest.split_once('<').unwrap_or((rest, ""));
+        let text = decode_entities(text);
+        match &mut pre {
+            Some(block) => block.push_str(&text),
+            None => markdown.push_str(&text.replace('\n', " ")),
+        }
+        let Some((tag, after)) = after.split_once('>') else {
+            break;
+        };
+        rest = after;
+
+        let tag = Tag::parse(tag);
+        match (tag.name.as_str(), tag.closing, &mut pre) {
+            ("pre", false, _) => pre = Some(String::new()),
+            ("pre", true, Some(block)) => {
+                markdown.push_str(&format!("\n```\n{}\n```\n\n", block.trim_end()));
+                examples.push(format!("{}\n", block.trim_end()));
+                pre = None;
+            }
+            (_, _, Some(_)) => {}
+            ("h2", false, _) => markdown.push_str("## "),
+            ("h2", true, _) | ("p", true, _) | ("ul", true, _) => markdown.push_str("\n\n"),
+            ("li", false, _) => markdown.push_str("- "),
+            ("li", true, _) => markdown.push('\n'),
+            ("em", _, _) => markdown.push_str("**"),
+            ("code", _, _) => markdown.push('`'),
+            ("a", false, _) => {
+                links.push(tag.href.unwrap_or_default().to_owned());
+                markdown.push('[');
+            }
+            ("a", true, _) => markdown.push_str(&format!("]({})", links.pop().unwrap_or_default())),
+            _ => {}
+        }
+    }
+
+    // Collapse the runs of empty lines and spaces the tags leave behind.
+    let mut collapsed = String::new();
+    let mut in_code = false;
+    let mut blank = 0;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+        }
+        let line = match in_code || line.starts_with("```") {
+            true => line.to_owned(),
+            false => line.split_whitespace().collect::<Vec<_>>().join(" "),
+        };
+        if line.is_empty() && !in_code {
+            blank += 1;
+            if blank > 1 {
+                continue;
+            }
+        } else {
+            blank = 0;
+        }
+        collapsed.push_str(&line);
+        collapsed.push('\n');
+    }
+    collapsed.trim().to_owned() + "\n"
+}
+
+/// Extract the parts of a puzzle from the html of its page.
+pub fn parse_puzzle(html: &str) -> Puzzle {
+    let mut puzzle = Puzzle::default();
+    let mut rest = html;
+    while let Some((_, article)) = rest.split_once("<article") {
+        let Some((article, after)) = article.split_once("</article>") else {
+            break;
+        };
+        rest = after;
+        let article = article.split_once('>').map(|(_, a)| a).unwrap_or(article);
+        puzzle
+            .parts
+            .push(article_to_markdown(article, &mut puzzle.examples));
+    }
+    puzzle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>Santa's reindeer typically eat regular reindeer food, but they need a lot of <a href="/2022/day/1#part2">magical energy</a> to deliver presents on Christmas.</p>
+<p>For example, suppose the Elves finish writing their items' <em>Calories</em> into the Calorie Counting list:</p>
+<pre><code>1000
+2000
+
+3000
+</code></pre>
+<ul>
+<li>The first Elf is carrying food with <code>1000</code> and <code>2000</code> Calories.</li>
+<li>The second Elf is carrying one food item with <code>3000</code> Calories.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>24000</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Find the top <em>three</em> Elves &amp; sum up their Calories &lt;= 45000.</p>
+<pre><code>a &lt;b&gt;
+</code></pre>
+</article>
+</main>
+</body>
+</html>"#;
+
+    #[test]
+    fn parses_both_parts() {
+        let puzzle = parse_puzzle(PAGE);
+        assert_eq!(puzzle.parts.len(), 2);
+        assert_eq!(
+            puzzle.parts[0],
+            "## --- Day 1: Calorie Counting ---\n\
+             \n\
+             Santa's reindeer typically eat regular reindeer food, but they need a lot of \
+             [magical energy](/2022/day/1#part2) to deliver presents on Christmas.\n\
+             \n\
+             For example, suppose the Elves finish writing their items' **Calories** into the Calorie Counting list:\n\
+             \n\
+             ```\n\
+             1000\n\
+             2000\n\
+             \n\
+             3000\n\
+             ```\n\
+             \n\
+             - The first Elf is carrying food with `1000` and `2000` Calories.\n\
+             - The second Elf is carrying one food item with `3000` Calories.\n"
+        );
+        assert_eq!(
+            puzzle.parts[1],
+            "## --- Part Two ---\n\
+             \n\
+             Find the top **three** Elves & sum up their Calories <= 45000.\n\
+             \n\
+             ```\n\
+             a <b>\n\
+             ```\n"
+        );
+        assert_eq!(puzzle.examples, ["1000\n2000\n\n3000\n", "a <b>\n"]);
+        assert_eq!(puzzle.markdown(), puzzle.parts.join("\n"));
+    }
+
+    #[test]
+    fn page_without_articles() {
+        let puzzle = parse_puzzle("<html><body><p>Please log in.</p></body></html>");
+        assert_eq!(puzzle, Puzzle::default());
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(
+            decode_entities("&quot;hi&quot; &apos;x&apos;&nbsp;y"),
+            "\"hi\" 'x' y"
+        );
+        assert_eq!(decode_entities("&#42; &#x2A; &#x1F384;"), "* * 🎄");
+    }
+
+    #[test]
+    fn keeps_unknown_entities() {
+        assert_eq!(decode_entities("fish & chips"), "fish & chips");
+        assert_eq!(
+            decode_entities("&unknown; &#xZZ; &#99999999;"),
+            "&unknown; &#xZZ; &#99999999;"
+        );
+        assert_eq!(
+            decode_entities("a &averyveryverylongname; b"),
+            "a &averyveryverylongname; b"
+        );
+        assert_eq!(decode_entities("&amp"), "&amp");
+    }
+}
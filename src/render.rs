@@ -0,0 +1,343 @@
+use crate::days::{Answer, Error};
+use crate::report::{self, Format, PartReport, RunReport, Status};
+use crate::{dynamic_range_time_format, Summary, TestReport};
+use colored::*;
+use std::time::Duration;
+
+/// Print the reports in a machine readable format, does nothing for [`Format::Text`].
+fn print_reports(
+    format: Format,
+    reports: &[RunReport],
+    budget: Option<Duration>,
+    wall_time: Option<Duration>,
+) {
+    match format {
+        Format::Text => {}
+        Format::Json => report::print_json(reports, budget, wall_time),
+        Format::Csv => report::print_csv(reports),
+    }
+}
+
+/// The answer of a part, or why there is none.
+fn part_answer(part: &PartReport) -> ColoredString {
+    match &part.answer {
+        Ok(answer) => format!("{}", answer).bold().blue(),
+        Err(err) => err.to_string().red().bold(),
+    }
+}
+
+/// ` ✓` or ` ✗` if the expected answer is known.
+fn mark(part: &PartReport) -> &'static str {
+    match part.passed() {
+        Some(true) => " ✓",
+        Some(false) => " ✗",
+        None => "",
+    }
+}
+
+fn print_error(err: &Error) {
+    match err {
+        Error::Parse(err) => println!("{}: {}", "Invalid input".red().bold(), err),
+        Error::Param(err) => println!("{}: {}", "Invalid parameter".red().bold(), err),
+        Error::Input(err) => println!("{}: {}", "Missing input".red().bold(), err),
+        Error::Timeout(_) | Error::NotImplemented(_) => {
+            println!("{}", err.to_string().red().bold())
+        }
+        Error::Panic(err) => println!("{}: {}", "Panicked".red().bold(), err),
+    }
+}
+
+/// Print the results of a day run on the real input.
+/// If the day couldn't be run, only the heading is printed as text, the error is left to the caller.
+pub fn print_run(report: &RunReport, format: Format) {
+    if format != Format::Text {
+        print_reports(format, std::slice::from_ref(report), None, None);
+        return;
+    }
+
+    println!("{} Day {}", "Starting".green().bold(), report.day);
+    println!("{}", "-----------------------".green().bold());
+    if report.error.is_some() {
+        return;
+    }
+
+    println!("{}:", "Results".green().bold());
+    println!(
+        "\t{}: {}",
+        "Parsing time".green(),
+        dynamic_range_time_format(&report.parse_time.unwrap_or_default())
+            .bold()
+            .blue()
+    );
+    if let Some(memory) = &report.parse_memory {
+        println!(
+            "\t{}: {}",
+            "Parsing memory".green(),
+            memory.to_string().bold().blue()
+        );
+    }
+    for (n, part) in report.parts() {
+        println!("\t{}:", format!("Part {}", n).green());
+        println!(
+            "\t\tSolution: {}{}",
+            part_answer(part),
+            match part.passed() {
+                Some(true) => mark(part).green().bold(),
+                _ => mark(part).red().bold(),
+            }
+        );
+        if let (Some(false), Some(expected)) = (part.passed(), &part.expected) {
+            println!("\t\tExpected: {}", format!("{}", expected).bold().blue());
+        }
+        println!(
+            "\t\tTook:     {}",
+            dynamic_range_time_format(&part.time).bold().blue()
+        );
+        if let Some(memory) = &part.memory {
+            println!("\t\tMemory:   {}", memory.to_string().bold().blue());
+        }
+    }
+}
+
+/// Print the results of every example of a day.
+pub fn print_test(report: &TestReport, format: Format) {
+    if format != Format::Text {
+        print_reports(format, &report.cases, None, None);
+        return;
+    }
+
+    println!("{} Day {}", "Testing".green().bold(), report.day);
+    println!("{}", "-----------------------".green().bold());
+    if report.cases.is_empty() {
+        println!(
+            "{}: add them to examples/d{:02}",
+            "No examples".yellow().bold(),
+            report.day
+        );
+        return;
+    }
+
+    for case in &report.cases {
+        println!("{} {}:", "Example".green().bold(), case.case);
+        for (key, value) in &case.params {
+            println!("\t{}: {} = {}", "Parameter".green(), key, value);
+        }
+        if let Some(err) = &case.error {
+            print_error(err);
+            continue;
+        }
+
+        for (n, part) in case.parts() {
+            let expected = part.expected.as_ref().unwrap();
+            println!(
+                "\t{}: {}",
+                format!("Part {}", n).green(),
+                match (part.passed(), &part.answer) {
+                    (Some(true), _) => "PASSED".green().bold(),
+                    (_, Err(Error::Timeout(_))) => "TIMEOUT".red().bold(),
+                    (_, Err(_)) => "ERROR".red().bold(),
+                    _ => "FAILED".red().bold(),
+                }
+            );
+            println!("\t\tResult:   {}", part_answer(part));
+            println!("\t\tExpected: {}", format!("{}", expected).bold().blue());
+        }
+    }
+}
+
+/// Shorten an answer so it fits into a single table cell.
+fn short_answer(answer: &Answer) -> String {
+    let s = match (answer, answer.text()) {
+        (Answer::Bitmap(_), Some(text)) => text,
+        (Answer::Bitmap(bm), None) => format!(
+            "[bitmap {}x{}]",
+            bm.first().map(|l| l.len()).unwrap_or(0),
+            bm.len()
+        ),
+        (Answer::Grid(grid), _) => format!(
+            "[grid {}x{}]",
+            grid.first().map(|l| l.len()).unwrap_or(0),
+            grid.len()
+        ),
+        (a, _) => a.to_string(),
+    };
+
+    if s.chars().count() > 20 {
+        s.chars().take(19).collect::<String>() + "…"
+    } else {
+        s
+    }
+}
+
+/// Describe a selection of days like `days 1-5, 7`.
+fn days_label(days: &[u8]) -> String {
+    if days.len() == 25 {
+        return "all days".to_owned();
+    }
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+    for day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *day => *end = *day,
+            _ => ranges.push((*day, *day)),
+        }
+    }
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect();
+    format!("days {}", ranges.join(", "))
+}
+
+/// Print a table of the results of multiple days, with the time they took together.
+pub fn print_summary(summary: &Summary, format: Format, budget: Option<Duration>) {
+    if format != Format::Text {
+        print_reports(format, &summary.reports, budget, Some(summary.wall_time));
+        return;
+    }
+
+    let action = match summary.examples {
+        true => "Testing",
+        false => "Running",
+    };
+    println!("{} {}", action.green().bold(), days_label(&summary.days));
+    println!("{}", "-----------------------".green().bold());
+
+    let answer = |r: &RunReport, n: u8| {
+        r.parts()
+            .find(|(i, _)| *i == n)
+            .map(|(_, p)| match &p.answer {
+                Ok(answer) => short_answer(answer) + mark(p),
+                Err(Error::Timeout(_)) => "TIMEOUT".to_owned(),
+                Err(_) => "ERROR".to_owned(),
+            })
+            .unwrap_or_default()
+    };
+    let part_time = |r: &RunReport, n: u8| {
+        r.parts()
+            .find(|(i, _)| *i == n)
+            .map(|(_, p)| dynamic_range_time_format(&p.time))
+            .unwrap_or_default()
+    };
+
+    let reports = &summary.reports;
+    let answer_width = reports
+        .iter()
+        .flat_map(|r| [answer(r, 1), answer(r, 2)])
+        .map(|a| a.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let case_width = reports
+        .iter()
+        .map(|r| r.case.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    println!(
+        "{}",
+        format!(
+            "{:>3}  {:<cw$} {:<10} {:<aw$} {:<10} {:<aw$} {:<10} Status",
+            "Day",
+            "Case",
+            "Parse",
+            "Part 1",
+            "Time",
+            "Part 2",
+            "Time",
+            aw = answer_width,
+            cw = case_width
+        )
+        .green()
+        .bold()
+    );
+
+    for report in reports {
+        let status = match report.status() {
+            Status::Passed => "PASSED".green().bold(),
+            Status::Failed => "FAILED".red().bold(),
+            Status::Done => "DONE".green(),
+            Status::Timeout => "TIMEOUT".red().bold(),
+            Status::Error => "ERROR".red().bold(),
+        };
+
+        println!(
+            "{:>3}  {:<cw$} {:<10} {} {:<10} {} {:<10} {}",
+            report.day,
+            report.case,
+            report
+                .parse_time
+                .map(|t| dynamic_range_time_format(&t))
+                .unwrap_or_default(),
+            format!("{:<aw$}", answer(report, 1), aw = answer_width)
+                .bold()
+                .blue(),
+            part_time(report, 1),
+            format!("{:<aw$}", answer(report, 2), aw = answer_width)
+                .bold()
+                .blue(),
+            part_time(report, 2),
+            status,
+            cw = case_width
+        );
+    }
+
+    for report in reports {
+        if let Some(err) = &report.error {
+            println!(
+                "{} {} ({}): {}",
+                "Day".red().bold(),
+                report.day,
+                report.case,
+                err
+            );
+        }
+        for (n, part) in report.parts() {
+            if let Err(err) = &part.answer {
+                println!(
+                    "{} {} ({}) part {}: {}",
+                    "Day".red().bold(),
+                    report.day,
+                    report.case,
+                    n,
+                    err
+                );
+            }
+        }
+    }
+
+    let total = summary.total_time();
+    println!();
+    println!(
+        "{}: {}",
+        "Total time".green(),
+        dynamic_range_time_format(&total).bold().blue()
+    );
+    println!(
+        "{}: {}{}",
+        "Wall-clock time".green(),
+        dynamic_range_time_format(&summary.wall_time).bold().blue(),
+        match summary.jobs {
+            1 => String::new(),
+            jobs => format!(
+                " ({:.1}x speedup with {} jobs)",
+                total.as_secs_f64() / summary.wall_time.as_secs_f64().max(f64::EPSILON),
+                jobs
+            ),
+        }
+    );
+    if let Some(budget) = budget {
+        println!(
+            "{}: {} {}",
+            "Budget".green(),
+            dynamic_range_time_format(&budget).bold().blue(),
+            match summary.within_budget(budget) {
+                true => "OK".green().bold(),
+                false => "EXCEEDED".red().bold(),
+            }
+        );
+    }
+}
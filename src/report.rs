@@ -0,0 +1,389 @@
+use crate::days::{Answer, Error};
+use crate::memory::AllocStats;
+use crate::Part;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Colored text meant for humans.
+    Text,
+    /// A single JSON document, see [`print_json`].
+    Json,
+    /// One CSV line per part, see [`print_csv`].
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "unknown format `{}`, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// The result of a single part.
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    /// The error is a timeout or a panic.
+    pub answer: Result<Answer, Error>,
+    /// From the example, or from the ledger for the real input, `None` if unknown.
+    pub expected: Option<Answer>,
+    pub time: Duration,
+    /// Only counted with the `count-allocs` feature.
+    pub memory: Option<AllocStats>,
+}
+
+impl PartReport {
+    /// `None` if there is nothing to compare against.
+    pub fn passed(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(self.answer.as_ref() == Ok(expected))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Every answer matched the expected answer.
+    Passed,
+    /// At least one answer didn't match the expected answer.
+    Failed,
+    /// Answers were computed, but there was nothing to compare them with.
+    Done,
+    /// A part took longer than the time limit.
+    Timeout,
+    /// The day couldn't be run.
+    Error,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Done => "done",
+            Self::Timeout => "timeout",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// The results of running a day on a single input, either the real input or an example.
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub day: u8,
+    /// `input` for the real input, otherwise the name of the example.
+    pub case: String,
+    /// The parts which were run.
+    pub part: Part,
+    /// The parameters given to the day, besides its defaults.
+    pub params: Vec<(String, String)>,
+    pub parse_time: Option<Duration>,
+    /// Only counted with the `count-allocs` feature.
+    pub parse_memory: Option<AllocStats>,
+    pub one: Option<PartReport>,
+    pub two: Option<PartReport>,
+    pub error: Option<Error>,
+}
+
+impl RunReport {
+    pub fn new(day: u8, case: &str, part: Part) -> Self {
+        Self {
+            day,
+            case: case.to_owned(),
+            part,
+            params: Vec::new(),
+            parse_time: None,
+            parse_memory: None,
+            one: None,
+            two: None,
+            error: None,
+        }
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &PartReport)> {
+        [(1, &self.one), (2, &self.two)]
+            .into_iter()
+            .filter_map(|(n, p)| Some((n, p.as_ref()?)))
+    }
+
+    pub fn status(&self) -> Status {
+        if self.error.is_some() {
+            return Status::Error;
+        }
+        let errors: Vec<&Error> = self
+            .parts()
+            .filter_map(|(_, p)| p.answer.as_ref().err())
+            .collect();
+        if errors.iter().any(|e| !matches!(e, Error::Timeout(_))) {
+            return Status::Error;
+        }
+        if !errors.is_empty() {
+            return Status::Timeout;
+        }
+        let passed: Vec<bool> = self.parts().filter_map(|(_, p)| p.passed()).collect();
+        if passed.is_empty() {
+            Status::Done
+        } else if passed.iter().all(|p| *p) {
+            Status::Passed
+        } else {
+            Status::Failed
+        }
+    }
+
+    /// The report, or the error if the day couldn't be run.
+    pub fn into_result(mut self) -> Result<Self, Error> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(self),
+        }
+    }
+
+    /// Time spent parsing and computing all parts.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time.unwrap_or_default() + self.parts().map(|(_, p)| p.time).sum::<Duration>()
+    }
+
+    /// The numbers of the parts which were requested.
+    fn part_numbers(&self) -> Vec<u8> {
+        match self.part {
+            Part::One => vec![1],
+            Part::Two => vec![2],
+            Part::Both => vec![1, 2],
+        }
+    }
+}
+
+/// Bitmaps are written as one string per row, using `#` and `.`.
+fn bitmap_rows(bm: &[Vec<bool>]) -> Vec<String> {
+    bm.iter()
+        .map(|row| row.iter().map(|p| if *p { '#' } else { '.' }).collect())
+        .collect()
+}
+
+fn grid_rows(grid: &[Vec<char>]) -> Vec<String> {
+    grid.iter().map(|row| row.iter().collect()).collect()
+}
+
+fn answer_type(answer: &Answer) -> &'static str {
+    match answer {
+        Answer::Number(_) => "number",
+        Answer::Signed(_) => "signed",
+        Answer::Wide(_) | Answer::WideUnsigned(_) => "wide",
+        Answer::String(_) => "string",
+        Answer::Bitmap(_) => "bitmap",
+        Answer::Grid(_) => "grid",
+    }
+}
+
+fn answer_json(answer: &Answer) -> Value {
+    let value = match answer {
+        Answer::Number(n) => json!(n),
+        Answer::Signed(n) => json!(n),
+        // Most JSON parsers lose precision above 64 bits.
+        Answer::Wide(n) => json!(n.to_string()),
+        Answer::WideUnsigned(n) => json!(n.to_string()),
+        Answer::String(s) => json!(s),
+        Answer::Bitmap(bm) => json!(bitmap_rows(bm)),
+        Answer::Grid(grid) => json!(grid_rows(grid)),
+    };
+    match answer {
+        Answer::Bitmap(_) => json!({
+            "type": answer_type(answer),
+            "value": value,
+            "text": answer.text(),
+        }),
+        _ => json!({ "type": answer_type(answer), "value": value }),
+    }
+}
+
+/// The answer as a single line of text, bitmap and grid rows are separated by `/`.
+fn answer_text(answer: &Answer) -> String {
+    match answer {
+        Answer::Bitmap(bm) => bitmap_rows(bm).join("/"),
+        Answer::Grid(grid) => grid_rows(grid).join("/"),
+        a => a.to_string(),
+    }
+}
+
+/// One record per requested part, errors are repeated for every part.
+fn records(reports: &[RunReport]) -> Vec<(&RunReport, u8, Option<&PartReport>)> {
+    reports
+        .iter()
+        .flat_map(|r| {
+            r.part_numbers().into_iter().filter_map(move |n| {
+                let part = match n {
+                    1 => r.one.as_ref(),
+                    _ => r.two.as_ref(),
+                };
+                match (part, &r.error) {
+                    (None, None) => None,
+                    _ => Some((r, n, part)),
+                }
+            })
+        })
+        .collect()
+}
+
+fn record_status(report: &RunReport, part: Option<&PartReport>) -> Status {
+    match part.and_then(|p| p.passed()) {
+        _ if report.error.is_some() => Status::Error,
+        _ if part.is_some_and(|p| matches!(p.answer, Err(Error::Timeout(_)))) => Status::Timeout,
+        _ if part.is_some_and(|p| p.answer.is_err()) => Status::Error,
+        Some(true) => Status::Passed,
+        Some(false) => Status::Failed,
+        None => Status::Done,
+    }
+}
+
+/// The error of the day, or of the part if the day could be run.
+fn record_error(report: &RunReport, part: Option<&PartReport>) -> Option<String> {
+    report
+        .error
+        .as_ref()
+        .or(part.and_then(|p| p.answer.as_ref().err()))
+        .map(|e| e.to_string())
+}
+
+/// Print the reports as JSON. The schema is stable, new fields may be added,
+/// but existing ones are never changed or removed:
+///
+/// ```text
+/// {
+///   "version": 1,
+///   "results": [{
+///     "day": 1,
+///     "case": "input",                            // or the name of the example
+///     "part": 1,
+///     "status": "passed",                         // passed, failed, done, timeout or error
+///     "answer": {"type": "number", "value": 24000}, // null on error or timeout
+///     "expected": null,                           // same as answer, null if unknown
+///     "parse_ns": 5191,                           // null if the day couldn't be run
+///     "time_ns": 257,                             // also set if the part failed, null if the day couldn't be run
+///     "parse_memory": {"allocations": 3, "bytes": 96, "peak_bytes": 64},
+///     "memory": null,                             // both null without the `count-allocs` feature
+///     "error": null                               // the error message
+///   }],
+///   "total_ns": 5448,                             // summed over all cases
+///   "wall_ns": 3120,                              // null for a single day
+///   "budget_ns": null
+/// }
+/// ```
+///
+/// Answers are of type `number`, `signed`, `wide`, `string`, `bitmap` or `grid`. Wide answers are strings,
+/// so no precision is lost. Bitmaps are an array of rows using `#` and `.`, with an additional `text` field
+/// containing the letters they show, or null if they couldn't be read. Grids are an array of rows as well.
+pub fn print_json(reports: &[RunReport], budget: Option<Duration>, wall_time: Option<Duration>) {
+    let results: Vec<Value> = records(reports)
+        .into_iter()
+        .map(|(report, n, part)| {
+            let status = record_status(report, part);
+            json!({
+                "day": report.day,
+                "case": report.case,
+                "part": n,
+                "status": status.name(),
+                "answer": part.and_then(|p| p.answer.as_ref().ok()).map(answer_json),
+                "expected": part.and_then(|p| p.expected.as_ref()).map(answer_json),
+                "parse_ns": report.parse_time.map(|t| t.as_nanos() as u64),
+                "time_ns": part.map(|p| p.time.as_nanos() as u64),
+                "parse_memory": report.parse_memory.as_ref().map(memory_json),
+                "memory": part.and_then(|p| p.memory.as_ref()).map(memory_json),
+                "error": record_error(report, part),
+            })
+        })
+        .collect();
+
+    let total: Duration = reports.iter().map(|r| r.total_time()).sum();
+    let document = json!({
+        "version": 1,
+        "results": results,
+        "total_ns": total.as_nanos() as u64,
+        "wall_ns": wall_time.map(|t| t.as_nanos() as u64),
+        "budget_ns": budget.map(|b| b.as_nanos() as u64),
+    });
+    println!("{}", serde_json::to_string_pretty(&document).unwrap());
+}
+
+fn memory_json(memory: &AllocStats) -> Value {
+    json!({
+        "allocations": memory.allocations,
+        "bytes": memory.bytes,
+        "peak_bytes": memory.peak,
+    })
+}
+
+/// The allocations, bytes and peak bytes as CSV fields, empty if they weren't counted.
+fn memory_fields(memory: Option<&AllocStats>) -> [String; 3] {
+    match memory {
+        Some(m) => [m.allocations, m.bytes, m.peak].map(|n| n.to_string()),
+        None => Default::default(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// Print the reports as CSV, with a header and one line per part:
+///
+/// `day,case,part,status,answer_type,answer,expected,parse_ns,time_ns,error,`
+/// `parse_allocations,parse_bytes,parse_peak_bytes,allocations,bytes,peak_bytes`
+///
+/// The fields have the same meaning as in [`print_json`], empty fields are null.
+/// Bitmap and grid answers are written as their rows separated by `/`.
+pub fn print_csv(reports: &[RunReport]) {
+    println!(
+        "day,case,part,status,answer_type,answer,expected,parse_ns,time_ns,error,\
+         parse_allocations,parse_bytes,parse_peak_bytes,allocations,bytes,peak_bytes"
+    );
+    for (report, n, part) in records(reports) {
+        let status = record_status(report, part);
+        let answer = part.and_then(|p| p.answer.as_ref().ok());
+        let fields: Vec<String> = [
+            report.day.to_string(),
+            report.case.clone(),
+            n.to_string(),
+            status.name().to_owned(),
+            answer
+                .map(|a| answer_type(a).to_owned())
+                .unwrap_or_default(),
+            answer.map(answer_text).unwrap_or_default(),
+            part.and_then(|p| p.expected.as_ref())
+                .map(answer_text)
+                .unwrap_or_default(),
+            report
+                .parse_time
+                .map(|t| t.as_nanos().to_string())
+                .unwrap_or_default(),
+            part.map(|p| p.time.as_nanos().to_string())
+                .unwrap_or_default(),
+            record_error(report, part).unwrap_or_default(),
+        ]
+        .into_iter()
+        .chain(memory_fields(report.parse_memory.as_ref()))
+        .chain(memory_fields(part.and_then(|p| p.memory.as_ref())))
+        .collect();
+        println!(
+            "{}",
+            fields
+                .iter()
+                .map(|f| csv_field(f))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The templates are in `templates/`, `{{day}}` is replaced by the day number.
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+const PLACEHOLDER_TEMPLATE: &str = include_str!("../templates/placeholder.rs");
+const EXPECTED_TEMPLATE: &str = include_str!("../templates/example.expected");
+
+fn render(template: &str, day: u8) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Whether a day module is still a placeholder, or was generated but hasn't been changed since.
+fn is_placeholder(code: &str, day: u8) -> bool {
+    let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    [PLACEHOLDER_TEMPLATE, DAY_TEMPLATE]
+        .iter()
+        .any(|t| normalize(&render(t, day)) == normalize(code))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    }
+    fs::write(path, content).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Generate the module of a day and an example for it in the source tree at `root`,
+/// the example input is empty if none is given. Returns the created files.
+///
+/// Refuses to replace a module which contains real code, existing examples are kept.
+pub fn new_day(root: &Path, day: u8, example: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err("The day must be between 1 and 25.".to_owned());
+    }
+
+    let module = root.join("src/days").join(format!("d{:02}.rs", day));
+    match fs::read_to_string(&module) {
+        Ok(code) if !is_placeholder(&code, day) => {
+            return Err(format!(
+                "{} already contains code, remove it first to start over",
+                module.display()
+            ))
+        }
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("{}: {}", module.display(), err))
+        }
+        _ => {}
+    }
+
+    let mut created = Vec::new();
+    write(&module, &render(DAY_TEMPLATE, day))?;
+    created.push(module);
+
+    let examples = root.join("src/days/examples").join(format!("d{:02}", day));
+    let files = [
+        ("example.txt", example.unwrap_or_default().to_owned()),
+        ("example.expected", render(EXPECTED_TEMPLATE, day)),
+    ];
+    for (name, content) in files {
+        let path = examples.join(name);
+        if !path.exists() {
+            write(&path, &content)?;
+            created.push(path);
+        }
+    }
+
+    Ok(created)
+}
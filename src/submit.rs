@@ -0,0 +1,321 @@
+use crate::tsv::{self, Names};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint whether it was too high or too low.
+    Wrong,
+    /// The part has already been solved, so the answer wasn't checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, `None` if the wait time couldn't be read.
+    RateLimited(Option<Duration>),
+    /// The response couldn't be understood, contains the text of the response.
+    Unknown(String),
+}
+
+/// The names used in the attempts file, only outcomes which say something about
+/// the answer itself are recorded.
+const OUTCOME_NAMES: Names<Outcome> = Names(&[
+    (Outcome::Correct, "correct"),
+    (Outcome::TooHigh, "too-high"),
+    (Outcome::TooLow, "too-low"),
+    (Outcome::Wrong, "wrong"),
+]);
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Self::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Self::Wrong => write!(f, "That's not the right answer."),
+            Self::AlreadySolved => write!(f, "This part has already been solved."),
+            Self::RateLimited(Some(wait)) => write!(
+                f,
+                "An answer was submitted too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Self::RateLimited(None) => write!(f, "An answer was submitted too recently."),
+            Self::Unknown(text) => write!(f, "Unexpected response: {}", text),
+        }
+    }
+}
+
+/// Remove all html tags and collapse whitespace.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Read a wait time like `1m 23s` or `38s` from the text following "You have".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    let mut secs = 0;
+    for part in wait.split_whitespace() {
+        let split = part.find(|c: char| !c.is_ascii_digit())?;
+        let (value, unit) = part.split_at(split);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Parse the html page returned after submitting an answer.
+pub fn parse_response(html: &str) -> Outcome {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, a)| a.split_once("</article>"))
+        .map(|(a, _)| a)
+        .unwrap_or(html);
+    let text = strip_tags(article.split_once('>').map(|(_, a)| a).unwrap_or(article));
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text))
+    } else {
+        Outcome::Unknown(text.chars().take(200).collect())
+    }
+}
+
+/// A previously submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// All answers which have been submitted, stored as one tab separated
+/// `day part outcome answer` line per attempt.
+#[derive(Debug, Clone)]
+pub struct Attempts {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Load the attempts from `path`, without the file there are none yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Ok(Self {
+            path: path.to_owned(),
+            attempts: tsv::load(path, "invalid attempt", Self::parse_line)?,
+        })
+    }
+
+    fn parse_line(line: &str) -> Option<Attempt> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Attempt {
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            outcome: OUTCOME_NAMES.parse(fields.next()?)?,
+            answer: fields.next()?.to_owned(),
+        })
+    }
+
+    /// Attempts for a single part of a day.
+    pub fn of(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// The correct answer of a part, if it has been found.
+    pub fn correct(&self, day: u8, part: u8) -> Option<&str> {
+        self.of(day, part)
+            .find(|a| a.outcome == Outcome::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Why `answer` is known to be wrong without submitting it, if it is.
+    /// Besides answers that were already rejected, numbers outside of the
+    /// bounds given by earlier "too high" or "too low" answers are wrong too.
+    pub fn known_wrong(&self, day: u8, part: u8, answer: &str) -> Option<String> {
+        if let Some(correct) = self.correct(day, part) {
+            if correct != answer {
+                return Some(format!("the correct answer is already known: {}", correct));
+            }
+        }
+
+        let number = answer.parse::<i128>().ok();
+        for attempt in self.of(day, part) {
+            if attempt.answer == answer && attempt.outcome != Outcome::Correct {
+                return Some(format!("`{}` was already rejected", answer));
+            }
+            let (Some(number), Ok(other)) = (number, attempt.answer.parse::<i128>()) else {
+                continue;
+            };
+            match attempt.outcome {
+                Outcome::TooHigh if number >= other => {
+                    return Some(format!("{} was already too high", other));
+                }
+                Outcome::TooLow if number <= other => {
+                    return Some(format!("{} was already too low", other));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Remember an attempt, outcomes which don't tell anything about the answer are ignored.
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), String> {
+        let Some(name) = OUTCOME_NAMES.name(&attempt.outcome) else {
+            return Ok(());
+        };
+        tsv::append(
+            &self.path,
+            &[format!(
+                "{}\t{}\t{}\t{}",
+                attempt.day, attempt.part, name, attempt.answer
+            )],
+        )?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wrap the article of a response in the rest of the page, like AoC sends it.
+    fn page(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n<meta charset=\"utf-8\"/>\n\
+             <title>Day 1 - Advent of Code 2022</title>\n</head><!--\n\n\n\n-->\n<body>\n\
+             <header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1>\
+             <div class=\"user\">someone <span class=\"star-count\">12*</span></div></div></header>\n\
+             <main>\n{}\n</main>\n</body>\n</html>",
+            article
+        )
+    }
+
+    #[test]
+    fn correct() {
+        let html = page(
+            "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to collecting enough star fruit. <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::Correct);
+    }
+
+    #[test]
+    fn wrong() {
+        let html = page(
+            "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data; \
+             there are also some general tips on the <a href=\"/2022/about\">about page</a>, or you can ask for hints \
+             on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  \
+             Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::Wrong);
+    }
+
+    #[test]
+    fn too_high() {
+        let html = page(
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're \
+             using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, \
+             or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  \
+             Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::TooHigh);
+    }
+
+    #[test]
+    fn too_low() {
+        let html = page(
+            "<article><p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're \
+             using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, \
+             or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  \
+             Please wait 5 minutes before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::TooLow);
+    }
+
+    #[test]
+    fn rate_limited() {
+        let html = page(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying \
+             again.  You have 34s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(
+            parse_response(&html),
+            Outcome::RateLimited(Some(Duration::from_secs(34)))
+        );
+
+        let html = page(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying \
+             again.  You have 4m 12s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(
+            parse_response(&html),
+            Outcome::RateLimited(Some(Duration::from_secs(252)))
+        );
+    }
+
+    #[test]
+    fn rate_limited_without_wait_time() {
+        let html = page(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying \
+             again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::RateLimited(None));
+    }
+
+    #[test]
+    fn already_solved() {
+        let html = page(
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it? \
+             <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert_eq!(parse_response(&html), Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn unknown() {
+        let html =
+            page("<p>Puzzle inputs differ by user.  Please log in to get your puzzle input.</p>");
+        let Outcome::Unknown(text) = parse_response(&html) else {
+            panic!("expected an unknown outcome");
+        };
+        assert!(text.contains("Please log in"));
+        assert!(!text.contains('<'));
+    }
+}
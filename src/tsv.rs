@@ -0,0 +1,127 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+// The answers, attempts and benchmark results in the cache directory are stored as
+// one tab separated record per line, so they can be read and edited by hand.
+
+/// Parse every record of the file at `path`, a missing file has none.
+/// Empty lines and lines starting with `#` are skipped, a line `parse` returns `None` for
+/// is an error, described as `invalid`.
+pub fn load<T>(
+    path: &Path,
+    invalid: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<T>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("{}: {}", path.display(), err)),
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|(i, line)| {
+            parse(line).ok_or_else(|| format!("{}, line {}: {}", path.display(), i + 1, invalid))
+        })
+        .collect()
+}
+
+fn create_parent(path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+}
+
+/// Add records to the end of the file, creating it if necessary.
+pub fn append(path: &Path, records: &[String]) -> Result<(), String> {
+    create_parent(path);
+    let lines: String = records.iter().map(|r| format!("{}\n", r)).collect();
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Replace the file with `records`.
+pub fn write(path: &Path, records: &[String]) -> Result<(), String> {
+    create_parent(path);
+    let lines: String = records.iter().map(|r| format!("{}\n", r)).collect();
+    fs::write(path, lines).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// The names values of an enum have in a file, in both directions.
+pub struct Names<T: 'static>(pub &'static [(T, &'static str)]);
+
+impl<T: PartialEq + Clone> Names<T> {
+    /// The name of `value`, `None` if it isn't stored.
+    pub fn name(&self, value: &T) -> Option<&'static str> {
+        self.0.iter().find(|(v, _)| v == value).map(|(_, n)| *n)
+    }
+
+    pub fn parse(&self, name: &str) -> Option<T> {
+        self.0
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(v, _)| v.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_and_loads_records() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc22-test-{}-tsv", std::process::id()))
+            .join("records.tsv");
+        let parse = |line: &str| {
+            let (day, name) = line.split_once('\t')?;
+            Some((day.parse::<u8>().ok()?, name.to_owned()))
+        };
+        assert_eq!(load(&path, "invalid record", parse), Ok(vec![]));
+
+        write(&path, &["1\tone".to_owned()]).unwrap();
+        append(&path, &["2\ttwo".to_owned(), "# comment".to_owned()]).unwrap();
+        append(&path, &["".to_owned(), "3\tthree\tfields".to_owned()]).unwrap();
+        assert_eq!(
+            load(&path, "invalid record", parse),
+            Ok(vec![
+                (1, "one".to_owned()),
+                (2, "two".to_owned()),
+                (3, "three\tfields".to_owned())
+            ])
+        );
+
+        append(&path, &["x\tfour".to_owned()]).unwrap();
+        assert_eq!(
+            load(&path, "invalid record", parse),
+            Err(format!("{}, line 6: invalid record", path.display()))
+        );
+
+        write(&path, &[]).unwrap();
+        assert_eq!(load(&path, "invalid record", parse), Ok(vec![]));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn names() {
+        #[derive(Debug, Clone, PartialEq)]
+        enum Kind {
+            A,
+            B,
+            Unstored,
+        }
+        const NAMES: Names<Kind> = Names(&[(Kind::A, "a"), (Kind::B, "b")]);
+
+        assert_eq!(NAMES.name(&Kind::B), Some("b"));
+        assert_eq!(NAMES.name(&Kind::Unstored), None);
+        assert_eq!(NAMES.parse("a"), Some(Kind::A));
+        assert_eq!(NAMES.parse("c"), None);
+    }
+}
@@ -0,0 +1,279 @@
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The source of the current time, so waiting for a puzzle can be tested without waiting for December.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// The real time shifted to start at a given instant, time still passes normally.
+pub struct OffsetClock {
+    start: SystemTime,
+    real_start: SystemTime,
+}
+
+impl OffsetClock {
+    pub fn starting_at(start: SystemTime) -> Self {
+        Self {
+            start,
+            real_start: SystemTime::now(),
+        }
+    }
+}
+
+impl Clock for OffsetClock {
+    fn now(&self) -> SystemTime {
+        self.start + self.real_start.elapsed().unwrap_or_default()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on the day of December.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let secs = days_from_civil(year as i64, 12, day as i64) * 86400 + 5 * 3600;
+    UNIX_EPOCH + Duration::from_secs(secs as u64)
+}
+
+fn format_countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    let (days, hours, minutes, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    match days {
+        0 => format!("{:02}:{:02}:{:02}", hours, minutes, secs),
+        _ => format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, secs),
+    }
+}
+
+/// Wait until the puzzle is unlocked, showing a countdown on stderr.
+pub fn wait_for_unlock(clock: &dyn Clock, year: u16, day: u8) {
+    let unlock = unlock_time(year, day);
+    let mut waited = false;
+    while let Ok(left) = unlock.duration_since(clock.now()) {
+        if left.is_zero() {
+            break;
+        }
+        eprint!(
+            "\rDay {} unlocks in {} ",
+            day,
+            format_countdown(left + Duration::from_millis(999))
+        );
+        let _ = std::io::stderr().flush();
+        waited = true;
+        clock.sleep(left.min(Duration::from_secs(1)));
+    }
+    if waited {
+        eprintln!("\rDay {} is unlocked!{}", day, " ".repeat(16));
+    }
+}
+
+/// Why fetching something failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// It might work later, like when the puzzle isn't unlocked yet or the server is overloaded.
+    Retry(String),
+    /// Trying again won't help, like with an invalid session.
+    Fatal(String),
+}
+
+/// How often and how long to wait between attempts, the wait doubles after each attempt.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub attempts: usize,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            attempts: 8,
+        }
+    }
+}
+
+/// Call `fetch` until it succeeds, fails fatally or runs out of attempts.
+pub fn fetch_with_retry<T>(
+    clock: &dyn Clock,
+    backoff: &Backoff,
+    mut fetch: impl FnMut() -> Result<T, FetchError>,
+) -> Result<T, String> {
+    let mut wait = backoff.initial;
+    for attempt in 1.. {
+        match fetch() {
+            Ok(value) => return Ok(value),
+            Err(FetchError::Fatal(err)) => return Err(err),
+            Err(FetchError::Retry(err)) if attempt >= backoff.attempts => {
+                return Err(format!("{} (gave up after {} attempts)", err, attempt))
+            }
+            Err(FetchError::Retry(err)) => {
+                eprintln!("{}, trying again in {}s", err, wait.as_secs_f32());
+                clock.sleep(wait);
+                wait = (wait * 2).min(backoff.max);
+            }
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// A clock which only moves when something sleeps, and remembers how long.
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: SystemTime) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn sleeps(&self) -> Vec<Duration> {
+            self.sleeps.borrow().clone()
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        // 2022-12-01 05:00:00 UTC
+        assert_eq!(unlock_time(2022, 1), UNIX_EPOCH + secs(1669870800));
+        // 2015-12-01 05:00:00 UTC
+        assert_eq!(unlock_time(2015, 1), UNIX_EPOCH + secs(1448946000));
+        // 2024-12-25 05:00:00 UTC, after a leap day
+        assert_eq!(unlock_time(2024, 25), UNIX_EPOCH + secs(1735102800));
+    }
+
+    #[test]
+    fn waits_until_unlocked() {
+        let unlock = unlock_time(2022, 5);
+        let clock = FakeClock::at(unlock - Duration::from_millis(2500));
+        wait_for_unlock(&clock, 2022, 5);
+        assert_eq!(
+            clock.sleeps(),
+            [secs(1), secs(1), Duration::from_millis(500)]
+        );
+        assert_eq!(clock.now(), unlock);
+    }
+
+    #[test]
+    fn doesnt_wait_for_unlocked_puzzles() {
+        let clock = FakeClock::at(unlock_time(2022, 5) + secs(10));
+        wait_for_unlock(&clock, 2022, 5);
+        assert!(clock.sleeps().is_empty());
+
+        let clock = FakeClock::at(unlock_time(2022, 5));
+        wait_for_unlock(&clock, 2022, 5);
+        assert!(clock.sleeps().is_empty());
+    }
+
+    #[test]
+    fn countdown() {
+        assert_eq!(format_countdown(secs(59)), "00:00:59");
+        assert_eq!(format_countdown(secs(3 * 3600 + 25 * 60 + 7)), "03:25:07");
+        assert_eq!(format_countdown(secs(2 * 86400 + 3600)), "2d 01:00:00");
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let clock = FakeClock::at(UNIX_EPOCH);
+        let backoff = Backoff {
+            initial: secs(1),
+            max: secs(5),
+            attempts: 6,
+        };
+        let mut calls = 0;
+        let result: Result<(), String> = fetch_with_retry(&clock, &backoff, || {
+            calls += 1;
+            Err(FetchError::Retry("server overloaded".to_owned()))
+        });
+
+        assert_eq!(calls, 6);
+        assert_eq!(
+            result,
+            Err("server overloaded (gave up after 6 attempts)".to_owned())
+        );
+        assert_eq!(
+            clock.sleeps(),
+            [secs(1), secs(2), secs(4), secs(5), secs(5)]
+        );
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let clock = FakeClock::at(UNIX_EPOCH);
+        let mut calls = 0;
+        let result = fetch_with_retry(&clock, &Backoff::default(), || {
+            calls += 1;
+            match calls {
+                3 => Ok(calls),
+                _ => Err(FetchError::Retry("not unlocked yet".to_owned())),
+            }
+        });
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(clock.sleeps(), [secs(1), secs(2)]);
+    }
+
+    #[test]
+    fn fatal_errors_arent_retried() {
+        let clock = FakeClock::at(UNIX_EPOCH);
+        let mut calls = 0;
+        let result: Result<(), String> = fetch_with_retry(&clock, &Backoff::default(), || {
+            calls += 1;
+            Err(FetchError::Fatal("invalid session".to_owned()))
+        });
+
+        assert_eq!(calls, 1);
+        assert_eq!(result, Err("invalid session".to_owned()));
+        assert!(clock.sleeps().is_empty());
+    }
+}
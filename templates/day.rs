@@ -0,0 +1,41 @@
+use super::{Answer, Day, DayImpl, InputError, ParseError, ParseResult, RunContext};
+
+const CURRENT_DAY: u8 = {{day}};
+
+#[derive(Debug, Clone)]
+pub struct Line(String);
+
+impl<'a> TryFrom<&'a str> for Line {
+    type Error = InputError<'a>;
+
+    fn try_from(v: &'a str) -> ParseResult<'a, Self> {
+        if v.is_empty() {
+            return Err(InputError::new(v, "expected a line"));
+        }
+        Ok(Self(v.to_owned()))
+    }
+}
+
+type Data = Vec<Line>;
+impl DayImpl<Data> for Day<CURRENT_DAY> {
+    type Params = ();
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            input
+                .lines()
+                .map(Line::try_from)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
+    }
+
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        Answer::Number(data.iter().map(|l| l.0.len() as u64).sum())
+    }
+
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        Answer::Number(data.len() as u64)
+    }
+}
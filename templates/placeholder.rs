@@ -0,0 +1,31 @@
+use super::{number, Answer, Day, DayImpl, ParseError, ParseResult, RunContext};
+
+const CURRENT_DAY: u8 = {{day}};
+
+type Data = Vec<u64>;
+impl DayImpl<Data> for Day<CURRENT_DAY> {
+    type Params = ();
+
+    fn implemented() -> bool {
+        false
+    }
+
+    fn init(input: &str, _ctx: &RunContext<()>) -> Result<(Self, Data), ParseError> {
+        Ok((
+            Self {},
+            input
+                .lines()
+                .map(number)
+                .collect::<ParseResult<Data>>()
+                .map_err(|e| e.locate(CURRENT_DAY, input))?,
+        ))
+    }
+
+    fn one(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        Answer::Number(data.len() as u64)
+    }
+
+    fn two(&self, data: &mut Data, _ctx: &RunContext<()>) -> Answer {
+        Answer::Number(data.len() as u64)
+    }
+}